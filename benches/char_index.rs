@@ -1,4 +1,7 @@
-use char_index::IndexedChars;
+// benches are only built with a recent toolchain, the MSRV applies to the library alone
+#![allow(clippy::incompatible_msrv)]
+
+use char_index::{IndexedChars, OwnedIndexedChars};
 
 use core::hint::black_box;
 
@@ -39,5 +42,60 @@ pub fn perf(c: &mut Criterion) {
     println!("Vec<char>: {} bytes", 4 * indexed.chars().count());
 }
 
-criterion_group!(benches, perf);
+pub fn char_eq(c: &mut Criterion) {
+    let ascii = OwnedIndexedChars::new("e".repeat(1000));
+
+    let mut group = c.benchmark_group("compare ascii chars");
+
+    group.bench_function("char_eq_at", |b| {
+        b.iter(|| {
+            (0..1000)
+                .filter(|&i| ascii.char_eq_at(black_box(i), 'e'))
+                .count()
+        })
+    });
+    group.bench_function("get_char", |b| {
+        b.iter(|| {
+            (0..1000)
+                .filter(|&i| ascii.get_char(black_box(i)) == Some('e'))
+                .count()
+        })
+    });
+}
+
+pub fn string_eq(c: &mut Criterion) {
+    // pairs sharing a long prefix with equal byte lengths but differing char counts,
+    // so a byte comparison has to walk the whole prefix before finding the difference
+    let prefix = "aé💯b".repeat(1000);
+    let pairs: Vec<_> = (1..200)
+        .map(|n| {
+            (
+                OwnedIndexedChars::new(prefix.clone() + &"é".repeat(n)),
+                OwnedIndexedChars::new(prefix.clone() + &"e".repeat(n * 2)),
+            )
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("compare unequal strings");
+
+    group.bench_function("indexed_eq", |b| {
+        b.iter(|| {
+            pairs
+                .iter()
+                .filter(|(l, r)| black_box(l) == black_box(r))
+                .count()
+        })
+    });
+    // the comparison before char counts were checked, of the backing strings alone
+    group.bench_function("string_eq", |b| {
+        b.iter(|| {
+            pairs
+                .iter()
+                .filter(|(l, r)| black_box(l.as_string()) == black_box(r.as_string()))
+                .count()
+        })
+    });
+}
+
+criterion_group!(benches, perf, char_eq, string_eq);
 criterion_main!(benches);
@@ -1,6 +1,7 @@
 //! Module containing [`IndexedChars`] and its trait implementations
 
-use crate::IndexedCharsInner;
+use crate::{IndexedCharsInner, OwnedIndexedChars};
+use alloc::string::String;
 use core::{
     borrow::Borrow,
     cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd},
@@ -65,6 +66,22 @@ impl<'a> IndexedChars<'a> {
         self.inner.char_count(self.buf)
     }
 
+    /// Copies the backing `&str` into an [`OwnedIndexedChars`], cloning the existing index rather than rebuilding it.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("añb");
+    /// let owned = index.to_owned_indexed();
+    ///
+    /// assert_eq!(owned.get_char(1), index.get_char(1));
+    /// # for i in 0..4 { assert_eq!(owned.get_char(i), index.get_char(i)); }
+    /// ```
+    #[must_use]
+    pub fn to_owned_indexed(&self) -> OwnedIndexedChars {
+        OwnedIndexedChars::from_inner(String::from(self.buf), self.inner.clone())
+    }
+
     /// Returns a reference to the backing `&str`
     #[must_use]
     pub fn as_str(&self) -> &str {
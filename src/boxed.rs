@@ -0,0 +1,187 @@
+//! Module containing [`BoxedIndexedChars`] and its trait implementations
+
+use alloc::boxed::Box;
+use core::{
+    borrow::Borrow,
+    cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd},
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+
+use crate::{IndexedCharsInner, OwnedIndexedChars};
+
+/// A string whose char indices have been cached for ~O(1) char lookup. Boxed, read only variant.
+///
+/// This is constructed with [`OwnedIndexedChars::into_boxed`], and stores its string as a `Box<str>`
+/// with an index that has been shrunk to fit, so it holds no spare capacity. It is intended for long lived values such as caches.
+///
+/// This type mimics a `Box<str>` with its trait impls, including `Debug`, `Display`, `PartialEq` with `&str` `PartialOrd` with `&str`, `Hash`, and `AsRef`/`Borrow`.
+pub struct BoxedIndexedChars {
+    /// Backing string allocation
+    buf: Box<str>,
+    /// Char offsets index
+    inner: IndexedCharsInner,
+}
+
+impl BoxedIndexedChars {
+    /// Constructs a new [`BoxedIndexedChars`] from a string and an index that was built for that string,
+    /// shrinking the index to fit.
+    pub(crate) fn from_inner(buf: Box<str>, mut inner: IndexedCharsInner) -> Self {
+        inner.shrink_to_fit();
+
+        Self { buf, inner }
+    }
+
+    /// Indexes into the backing string to retrieve the nth codepoint.
+    ///
+    /// This operation has an average case of O(1), and a worst case of O(log n).
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("foo")).into_boxed();
+    ///
+    /// assert_eq!(s.get_char(1), Some('o'));
+    /// ```
+    #[must_use]
+    pub fn get_char(&self, index: usize) -> Option<char> {
+        self.inner.get_char(&self.buf, index)
+    }
+
+    /// Returns the number of chars present in the backing string, this operation is free thanks to
+    /// how [`BoxedIndexedChars`] is constructed
+    #[must_use]
+    pub fn char_count(&self) -> usize {
+        self.inner.char_count(&self.buf)
+    }
+
+    /// Returns a reference to the backing `Box<str>` as a `&str`.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.buf
+    }
+
+    /// Returns the amount of heap memory used by the string and its index in bytes.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añb")).into_boxed();
+    ///
+    /// // 4 bytes of string data, and 1 byte of index per char
+    /// assert_eq!(s.memory_footprint(), 7);
+    /// ```
+    #[must_use]
+    pub fn memory_footprint(&self) -> usize {
+        self.buf.len() + self.inner.memory_footprint()
+    }
+
+    /// Converts this value back into an [`OwnedIndexedChars`] so that it may be mutated, reusing the index.
+    #[must_use]
+    pub fn into_owned(self) -> OwnedIndexedChars {
+        OwnedIndexedChars::from_inner(self.buf.into_string(), self.inner)
+    }
+}
+
+// The following lines are all trait implementations made to mirror what str does, and be compatible with str
+
+impl Deref for BoxedIndexedChars {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.buf
+    }
+}
+
+impl AsRef<str> for BoxedIndexedChars {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl Borrow<str> for BoxedIndexedChars {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl fmt::Debug for BoxedIndexedChars {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        <str as fmt::Debug>::fmt(&self.buf, f)
+    }
+}
+
+impl fmt::Display for BoxedIndexedChars {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        <str as fmt::Display>::fmt(&self.buf, f)
+    }
+}
+
+impl Eq for BoxedIndexedChars {}
+
+impl PartialEq for BoxedIndexedChars {
+    fn eq(&self, other: &Self) -> bool {
+        self.buf.eq(&other.buf)
+    }
+}
+
+impl PartialEq<str> for BoxedIndexedChars {
+    fn eq(&self, other: &str) -> bool {
+        (*self.buf).eq(other)
+    }
+}
+
+impl PartialEq<BoxedIndexedChars> for str {
+    fn eq(&self, other: &BoxedIndexedChars) -> bool {
+        self.eq(&*other.buf)
+    }
+}
+
+impl Ord for BoxedIndexedChars {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.buf.cmp(&other.buf)
+    }
+}
+
+impl PartialOrd for BoxedIndexedChars {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialOrd<str> for BoxedIndexedChars {
+    fn partial_cmp(&self, other: &str) -> Option<Ordering> {
+        Some((*self.buf).cmp(other))
+    }
+}
+
+impl PartialOrd<BoxedIndexedChars> for str {
+    fn partial_cmp(&self, other: &BoxedIndexedChars) -> Option<Ordering> {
+        Some(self.cmp(&*other.buf))
+    }
+}
+
+impl Hash for BoxedIndexedChars {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.buf.hash(state);
+    }
+}
+
+#[test]
+fn no_spare_capacity() {
+    let mut s = OwnedIndexedChars::with_capacity_for(10_000, 5_000);
+
+    for _ in 0..1000 {
+        s.push_str("aé💯");
+    }
+
+    let boxed = s.into_boxed();
+
+    assert_eq!(boxed.inner.spare_capacity(), 0);
+    assert_eq!(
+        boxed.memory_footprint(),
+        boxed.len() + boxed.inner.memory_footprint()
+    );
+    assert_eq!(boxed.get_char(2999), Some('💯'));
+}
@@ -0,0 +1,86 @@
+//! Module containing [`IndexBuilder`]
+
+use alloc::string::String;
+
+use crate::{IndexedCharsInner, OwnedIndexedChars};
+
+/// A builder that incrementally constructs an [`OwnedIndexedChars`] as string data arrives.
+///
+/// The index is extended as each chunk is pushed, so finishing the builder does not need to walk the string again.
+///
+/// # Examples
+/// ```rust
+/// # use char_index::IndexBuilder;
+/// let mut builder = IndexBuilder::new();
+///
+/// builder.push_str("fo");
+/// builder.push_char('ö');
+/// builder.push_str("bar");
+///
+/// let s = builder.finish();
+///
+/// assert_eq!(s, *"foöbar");
+/// assert_eq!(s.get_char(3), Some('b'));
+/// ```
+#[derive(Debug, Default)]
+pub struct IndexBuilder {
+    /// String data pushed so far
+    buf: String,
+    /// Char offsets index of `buf`
+    inner: IndexedCharsInner,
+}
+
+impl IndexBuilder {
+    /// Constructs a new empty [`IndexBuilder`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a string slice to the end of the builder, extending the index.
+    pub fn push_str(&mut self, s: &str) {
+        self.inner.push_str(&self.buf, s);
+        self.buf.push_str(s);
+    }
+
+    /// Appends a char to the end of the builder, extending the index.
+    pub fn push_char(&mut self, c: char) {
+        self.inner
+            .push(self.inner.char_count(&self.buf), self.buf.len(), c);
+        self.buf.push(c);
+    }
+
+    /// Finishes building, returning the resulting [`OwnedIndexedChars`].
+    #[must_use]
+    pub fn finish(self) -> OwnedIndexedChars {
+        OwnedIndexedChars::from_inner(self.buf, self.inner)
+    }
+}
+
+#[test]
+fn chunked() {
+    use alloc::vec::Vec;
+    use rand::{thread_rng, Rng};
+
+    let s: String = (0..20_000)
+        .map(|i| char::from_u32(i * 7 % 20_000).unwrap())
+        .collect();
+
+    let chars: Vec<_> = s.char_indices().map(|(i, _)| i).collect();
+
+    for _ in 0..10 {
+        let mut builder = IndexBuilder::new();
+        let mut start = 0;
+
+        while start < chars.len() {
+            let end = (start + thread_rng().gen_range(1..600)).min(chars.len());
+            let end_byte = chars.get(end).copied().unwrap_or(s.len());
+
+            builder.push_str(&s[chars[start]..end_byte]);
+            start = end;
+        }
+
+        assert_eq!(builder.buf, s);
+        assert_eq!(builder.inner, IndexedCharsInner::new(&s));
+    }
+}
@@ -0,0 +1,164 @@
+//! Module containing [`CharCursor`], a cursor over the chars of an indexed string
+
+use crate::IndexedCharsInner;
+
+/// A cursor over the chars of an [`OwnedIndexedChars`](crate::OwnedIndexedChars), that tracks both its char index and byte offset.
+///
+/// This is constructed with [`OwnedIndexedChars::cursor`](crate::OwnedIndexedChars::cursor) or
+/// [`OwnedIndexedChars::cursor_at`](crate::OwnedIndexedChars::cursor_at). Stepping forwards
+/// is O(1) per char, and jumping to an arbitrary char with [`seek`][CharCursor::seek] uses the index.
+///
+/// The cursor is an [`Iterator`] over the remaining chars, so [`next`][Iterator::next] consumes a char.
+///
+/// # Examples
+/// ```rust
+/// # use char_index::OwnedIndexedChars;
+/// let s = OwnedIndexedChars::new(String::from("ñ = 42"));
+/// let mut cursor = s.cursor();
+///
+/// assert_eq!(cursor.next(), Some('ñ'));
+/// assert_eq!(cursor.peek(), Some(' '));
+///
+/// cursor.advance(3);
+/// assert_eq!(cursor.remaining(), "42");
+/// assert_eq!(cursor.position(), 4);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CharCursor<'a> {
+    /// Backing string
+    buf: &'a str,
+    /// Char offsets index of `buf`
+    inner: &'a IndexedCharsInner,
+    /// Char index of the next char
+    index: usize,
+    /// Byte offset of the next char
+    byte: usize,
+}
+
+impl<'a> CharCursor<'a> {
+    /// Constructs a new [`CharCursor`] over `buf` positioned at the char `index`, or returns `None` if `index` is greater than the char count.
+    pub(crate) fn new(buf: &'a str, inner: &'a IndexedCharsInner, index: usize) -> Option<Self> {
+        let byte = inner.char_boundary(buf, index)?;
+
+        Some(Self {
+            buf,
+            inner,
+            index,
+            byte,
+        })
+    }
+
+    /// Returns the char index of the next char, this is the char count once the cursor is at the end.
+    #[must_use]
+    pub fn position(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the byte offset of the next char, this is the byte length of the string once the cursor is at the end.
+    #[must_use]
+    pub fn byte_offset(&self) -> usize {
+        self.byte
+    }
+
+    /// Returns the next char without consuming it, or `None` if the cursor is at the end.
+    #[must_use]
+    pub fn peek(&self) -> Option<char> {
+        self.remaining().chars().next()
+    }
+
+    /// Returns the rest of the string from the cursor onwards.
+    #[must_use]
+    pub fn remaining(&self) -> &'a str {
+        &self.buf[self.byte..]
+    }
+
+    /// Moves the cursor forwards by `n` chars, stopping at the end of the string.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añ💯"));
+    /// let mut cursor = s.cursor();
+    ///
+    /// cursor.advance(2);
+    /// assert_eq!(cursor.peek(), Some('💯'));
+    ///
+    /// cursor.advance(10);
+    /// assert_eq!(cursor.position(), 3);
+    /// ```
+    pub fn advance(&mut self, n: usize) {
+        let count = self.inner.char_count(self.buf);
+
+        self.seek(self.index.saturating_add(n).min(count));
+    }
+
+    /// Moves the cursor to the char index `index`, which may be behind the cursor.
+    ///
+    /// The byte offset of `index` is found through the index, so this is ~O(1).
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than the char count.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añ💯"));
+    /// let mut cursor = s.cursor_at(2).unwrap();
+    ///
+    /// cursor.seek(1);
+    /// assert_eq!(cursor.next(), Some('ñ'));
+    /// assert_eq!(cursor.byte_offset(), 3);
+    /// ```
+    pub fn seek(&mut self, index: usize) {
+        self.byte = self
+            .inner
+            .char_boundary(self.buf, index)
+            .expect("char index out of bounds");
+        self.index = index;
+    }
+}
+
+impl Iterator for CharCursor<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+
+        self.index += 1;
+        self.byte += c.len_utf8();
+
+        Some(c)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.inner.char_count(self.buf) - self.index;
+
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for CharCursor<'_> {}
+
+#[test]
+fn next_matches_chars() {
+    use crate::OwnedIndexedChars;
+
+    let s = OwnedIndexedChars::new("aé💯b".repeat(200));
+    let mut cursor = s.cursor();
+
+    for (i, (byte, c)) in s.char_indices().enumerate() {
+        assert_eq!(cursor.position(), i);
+        assert_eq!(cursor.byte_offset(), byte);
+        assert_eq!(cursor.len(), 800 - i);
+        assert_eq!(cursor.next(), Some(c));
+    }
+
+    assert_eq!(cursor.next(), None);
+    assert_eq!(cursor.remaining(), "");
+
+    cursor.seek(300);
+    assert_eq!(
+        cursor.remaining(),
+        &s[s.char_indices().nth(300).unwrap().0..]
+    );
+}
@@ -0,0 +1,302 @@
+//! Module containing [`CharEdit`] and the diff algorithm behind [`OwnedIndexedChars::char_diff`](crate::OwnedIndexedChars::char_diff)
+
+use alloc::{vec, vec::Vec};
+
+/// A single step of a char level diff, as returned by [`OwnedIndexedChars::char_diff`](crate::OwnedIndexedChars::char_diff).
+///
+/// Applying the edits in order to the old string produces the new string: [`Keep`][CharEdit::Keep] and
+/// [`Delete`][CharEdit::Delete] consume the next char of the old string, while [`Keep`][CharEdit::Keep] and
+/// [`Insert`][CharEdit::Insert] produce the next char of the new string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CharEdit {
+    /// The char is present in both strings
+    Keep(char),
+    /// The char is only present in the new string
+    Insert(char),
+    /// The char is only present in the old string
+    Delete(char),
+}
+
+/// Computes a shortest edit script from `old` to `new` with Myers' diff algorithm, appending it to `edits`.
+///
+/// The common prefix and suffix are trimmed first, then the rest is split at a point on a shortest edit path
+/// found by searching from both ends at once, and both halves are diffed recursively.
+/// This takes O((n + m) * d) time and O(n + m) space, where d is the number of inserted and deleted chars.
+pub(crate) fn myers_diff(old: &[char], new: &[char], edits: &mut Vec<CharEdit>) {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    edits.extend(old[..prefix].iter().map(|&c| CharEdit::Keep(c)));
+
+    let (old, new) = (&old[prefix..], &new[prefix..]);
+
+    let suffix = old
+        .iter()
+        .rev()
+        .zip(new.iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let (old_mid, new_mid) = (&old[..old.len() - suffix], &new[..new.len() - suffix]);
+
+    if let Some((x, y)) = middle_point(old_mid, new_mid) {
+        myers_diff(&old_mid[..x], &new_mid[..y], edits);
+        myers_diff(&old_mid[x..], &new_mid[y..], edits);
+    } else {
+        edits.extend(old_mid.iter().map(|&c| CharEdit::Delete(c)));
+        edits.extend(new_mid.iter().map(|&c| CharEdit::Insert(c)));
+    }
+
+    edits.extend(old[old.len() - suffix..].iter().map(|&c| CharEdit::Keep(c)));
+}
+
+/// Converts a length into a signed diff coordinate.
+fn signed(n: usize) -> isize {
+    // slices never hold more than isize::MAX bytes, chars are larger than a byte
+    isize::try_from(n).unwrap_or_else(|_| unreachable!("slice length exceeds isize::MAX"))
+}
+
+/// Converts a diff coordinate that is known to be non negative back into an index.
+fn unsigned(n: isize) -> usize {
+    usize::try_from(n).unwrap_or_else(|_| unreachable!("negative diff coordinate"))
+}
+
+/// Finds a point `(x, y)` on a shortest edit path from `old` to `new` that splits it into two smaller problems,
+/// by extending the furthest reaching paths from the start and from the end until they overlap.
+///
+/// `old` and `new` must not share a common prefix or suffix. Returns `None` if they have no chars in common,
+/// in which case every char of `old` is deleted and every char of `new` inserted.
+fn middle_point(old: &[char], new: &[char]) -> Option<(usize, usize)> {
+    if old.is_empty() || new.is_empty() {
+        return None;
+    }
+
+    let (n, m) = (signed(old.len()), signed(new.len()));
+
+    let max_d = (n + m + 1) / 2;
+    let offset = max_d;
+    let len = unsigned(2 * max_d + 2);
+
+    // the furthest x reached on each diagonal k = x - y, indexed by k + offset, from the start and from the end.
+    // -1 marks diagonals that have not been reached yet
+    let mut forward = vec![-1_isize; len];
+    let mut backward = vec![-1_isize; len];
+
+    forward[unsigned(offset + 1)] = 0;
+    backward[unsigned(offset + 1)] = 0;
+
+    let delta = n - m;
+    // with an odd delta the paths overlap while extending forwards, and otherwise while extending backwards
+    let front = delta % 2 != 0;
+
+    // diagonals trimmed from either end of the search after their paths ran off the edge of the edit graph
+    let (mut k1_start, mut k1_end, mut k2_start, mut k2_end) = (0, 0, 0, 0);
+
+    for d in 0..max_d {
+        let mut k1 = -d + k1_start;
+
+        while k1 <= d - k1_end {
+            let k1_offset = unsigned(offset + k1);
+
+            let mut x1 = if k1 == -d || (k1 != d && forward[k1_offset - 1] < forward[k1_offset + 1])
+            {
+                forward[k1_offset + 1]
+            } else {
+                forward[k1_offset - 1] + 1
+            };
+            let mut y1 = x1 - k1;
+
+            while x1 < n && y1 < m && old[unsigned(x1)] == new[unsigned(y1)] {
+                x1 += 1;
+                y1 += 1;
+            }
+
+            forward[k1_offset] = x1;
+
+            if x1 > n {
+                k1_end += 2;
+            } else if y1 > m {
+                k1_start += 2;
+            } else if front {
+                let k2_offset = offset + delta - k1;
+
+                if (0..signed(len)).contains(&k2_offset) && backward[unsigned(k2_offset)] != -1 {
+                    let x2 = n - backward[unsigned(k2_offset)];
+
+                    if x1 >= x2 {
+                        return Some((unsigned(x1), unsigned(y1)));
+                    }
+                }
+            }
+
+            k1 += 2;
+        }
+
+        let mut k2 = -d + k2_start;
+
+        while k2 <= d - k2_end {
+            let k2_offset = unsigned(offset + k2);
+
+            let mut x2 =
+                if k2 == -d || (k2 != d && backward[k2_offset - 1] < backward[k2_offset + 1]) {
+                    backward[k2_offset + 1]
+                } else {
+                    backward[k2_offset - 1] + 1
+                };
+            let mut y2 = x2 - k2;
+
+            while x2 < n && y2 < m && old[unsigned(n - x2 - 1)] == new[unsigned(m - y2 - 1)] {
+                x2 += 1;
+                y2 += 1;
+            }
+
+            backward[k2_offset] = x2;
+
+            if x2 > n {
+                k2_end += 2;
+            } else if y2 > m {
+                k2_start += 2;
+            } else if !front {
+                let k1_offset = offset + delta - k2;
+
+                if (0..signed(len)).contains(&k1_offset) && forward[unsigned(k1_offset)] != -1 {
+                    let x1 = forward[unsigned(k1_offset)];
+                    let y1 = offset + x1 - k1_offset;
+
+                    if x1 >= n - x2 {
+                        return Some((unsigned(x1), unsigned(y1)));
+                    }
+                }
+            }
+
+            k2 += 2;
+        }
+    }
+
+    None
+}
+
+#[test]
+fn applies_to_both_strings() {
+    use crate::OwnedIndexedChars;
+    use alloc::string::String;
+
+    let cases = [
+        ("", "", 0),
+        ("abc", "", 0),
+        ("", "añ", 0),
+        ("kitten", "sitting", 4),
+        ("añ💯b", "ñ💯xb", 3),
+        ("aa", "a", 1),
+        ("ñoño", "ñoñoño", 4),
+        ("💯💯💯", "💯ñ💯", 2),
+    ];
+
+    for (old, new, kept) in cases {
+        let edits = OwnedIndexedChars::new(String::from(old))
+            .char_diff(&OwnedIndexedChars::new(String::from(new)));
+
+        let mut rebuilt_old = String::new();
+        let mut rebuilt_new = String::new();
+
+        for &edit in &edits {
+            match edit {
+                CharEdit::Keep(c) => {
+                    rebuilt_old.push(c);
+                    rebuilt_new.push(c);
+                }
+                CharEdit::Delete(c) => rebuilt_old.push(c),
+                CharEdit::Insert(c) => rebuilt_new.push(c),
+            }
+        }
+
+        assert_eq!(rebuilt_old, old);
+        assert_eq!(rebuilt_new, new);
+        assert_eq!(
+            edits
+                .iter()
+                .filter(|e| matches!(e, CharEdit::Keep(_)))
+                .count(),
+            kept
+        );
+    }
+}
+
+#[test]
+fn shortest_edit_script() {
+    use rand::{thread_rng, Rng};
+
+    /// The length of the longest common subsequence of `a` and `b`, by dynamic programming
+    fn lcs_len(a: &[char], b: &[char]) -> usize {
+        let mut row = vec![0; b.len() + 1];
+
+        for &x in a {
+            let mut diagonal = 0;
+
+            for (j, &y) in b.iter().enumerate() {
+                let above = row[j + 1];
+                row[j + 1] = if x == y {
+                    diagonal + 1
+                } else {
+                    above.max(row[j])
+                };
+                diagonal = above;
+            }
+        }
+
+        row[b.len()]
+    }
+
+    let alphabet = ['a', 'b', 'ñ', '💯'];
+    let mut rng = thread_rng();
+
+    for _ in 0..500 {
+        let mut random = |max: usize| -> Vec<char> {
+            let len = rng.gen_range(0..max);
+            (0..len)
+                .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+                .collect()
+        };
+
+        let (old, new) = (random(40), random(40));
+
+        let mut edits = Vec::new();
+        myers_diff(&old, &new, &mut edits);
+
+        let mut rebuilt_old = Vec::new();
+        let mut rebuilt_new = Vec::new();
+        let mut kept = 0;
+
+        for edit in edits {
+            match edit {
+                CharEdit::Keep(c) => {
+                    rebuilt_old.push(c);
+                    rebuilt_new.push(c);
+                    kept += 1;
+                }
+                CharEdit::Delete(c) => rebuilt_old.push(c),
+                CharEdit::Insert(c) => rebuilt_new.push(c),
+            }
+        }
+
+        assert_eq!(rebuilt_old, old);
+        assert_eq!(rebuilt_new, new);
+        assert_eq!(kept, lcs_len(&old, &new));
+    }
+}
+
+#[test]
+fn large_inputs() {
+    let old: Vec<char> = "aé💯b".repeat(25_000).chars().collect();
+    let mut new = old.clone();
+    new[10] = 'x';
+    new.insert(50_000, 'ñ');
+    new.truncate(99_000);
+
+    let mut edits = Vec::new();
+    myers_diff(&old, &new, &mut edits);
+
+    let changed = edits
+        .iter()
+        .filter(|e| !matches!(e, CharEdit::Keep(_)))
+        .count();
+    assert_eq!(changed, 2 + 1 + 1001);
+}
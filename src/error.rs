@@ -0,0 +1,97 @@
+//! Module containing the error types of `char_index`
+
+use alloc::string::String;
+use core::fmt;
+
+use crate::{IndexedCharsInner, OwnedIndexedChars};
+
+/// The error returned by [`OwnedIndexedChars::from_raw_parts`](crate::OwnedIndexedChars::from_raw_parts)
+/// when the index was not built for the given string.
+///
+/// Both parts are kept so they can be recovered with [`into_parts`][RawPartsError::into_parts].
+#[derive(Debug)]
+pub struct RawPartsError {
+    /// The string that was passed
+    pub(crate) buf: String,
+    /// The index that did not match `buf`
+    pub(crate) inner: IndexedCharsInner,
+}
+
+impl RawPartsError {
+    /// Returns the string and index that failed to match.
+    #[must_use]
+    pub fn into_parts(self) -> (String, IndexedCharsInner) {
+        (self.buf, self.inner)
+    }
+}
+
+impl fmt::Display for RawPartsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("char index does not match string")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RawPartsError {}
+
+/// The reason an index snapshot was rejected, as returned by [`IndexSnapshotError::kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum IndexSnapshotErrorKind {
+    /// The snapshot was written in a format version this version of the crate does not support, carrying the version tag found
+    UnsupportedVersion(u8),
+    /// The snapshot is truncated or its lengths are inconsistent
+    Malformed,
+    /// The snapshot is well formed, but was not taken of the given string
+    Mismatch,
+}
+
+/// The error returned by [`OwnedIndexedChars::attach_index`](crate::OwnedIndexedChars::attach_index)
+/// when the snapshot cannot be used for the given string.
+///
+/// The string is kept so it can be recovered with [`into_string`][IndexSnapshotError::into_string],
+/// or indexed from scratch with [`rebuild`][IndexSnapshotError::rebuild].
+#[derive(Debug)]
+pub struct IndexSnapshotError {
+    /// The string that was passed
+    pub(crate) buf: String,
+    /// Why the snapshot was rejected
+    pub(crate) kind: IndexSnapshotErrorKind,
+}
+
+impl IndexSnapshotError {
+    /// Returns why the snapshot was rejected, for example to rebuild a stale snapshot but reject a malformed one.
+    #[must_use]
+    pub fn kind(&self) -> IndexSnapshotErrorKind {
+        self.kind
+    }
+
+    /// Returns the string that the snapshot was to be attached to.
+    #[must_use]
+    pub fn into_string(self) -> String {
+        self.buf
+    }
+
+    /// Indexes the string from scratch, ignoring the rejected snapshot.
+    #[must_use]
+    pub fn rebuild(self) -> OwnedIndexedChars {
+        OwnedIndexedChars::new(self.buf)
+    }
+}
+
+impl fmt::Display for IndexSnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            IndexSnapshotErrorKind::UnsupportedVersion(v) => {
+                write!(f, "unsupported char index snapshot version {}", v)
+            }
+            IndexSnapshotErrorKind::Malformed => f.write_str("malformed char index snapshot"),
+            IndexSnapshotErrorKind::Mismatch => {
+                f.write_str("char index snapshot does not match string")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IndexSnapshotError {}
@@ -1,21 +1,35 @@
 //! Houses core implementation of char index.
 
+use crate::{BlockInfo, IndexSnapshotErrorKind};
 use alloc::vec::Vec;
+use core::ops::{Bound, Range, RangeBounds};
+
+/// The format version written at the start of index snapshots.
+pub(crate) const SNAPSHOT_VERSION: u8 = 1;
 
 /// The core type of `char_index`.
 /// This struct implements building a memory efficient index of char
 ///  locations, and a method to access that index.
-#[derive(Debug)]
-pub(crate) struct IndexedCharsInner {
+///
+/// It is exposed so that an index may be stored separately from the string it was built for,
+/// see [`OwnedIndexedChars::into_parts`](crate::OwnedIndexedChars::into_parts) and
+/// [`OwnedIndexedChars::from_raw_parts`](crate::OwnedIndexedChars::from_raw_parts), or serialized as bytes with
+/// [`OwnedIndexedChars::index_snapshot`](crate::OwnedIndexedChars::index_snapshot).
+/// The default value is the index of an empty (or any ascii) string.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexedCharsInner {
     /// The char offsets, stores the amount that a given char index must increment by to be in the correct range
     chars: Vec<u8>,
-    /// rollovers, stores the points where the offsets overflowed u8, so it may be binary searched to add `u8::MAX` * index_in_rollovers to the offset
+    /// rollovers, stores the points where the offsets overflowed u8, so it may be binary searched to add `u8::MAX` * `index_in_rollovers` to the offset
+    ///
+    /// these are full width char indices, so there is no upper limit on the size of string that may be indexed
     rollovers: Vec<usize>,
 }
 
 impl IndexedCharsInner {
     /// Computes a new char index from a backing string
-    pub(crate) fn new(s: &str) -> Self {
+    #[must_use]
+    pub fn new(s: &str) -> Self {
         // this is expensive but it lets us avoid big reallocs
         // it also lets us niche on ascii strings
         // maybe-TODO(ultrabear) replace with no-std bytecount::num_chars?
@@ -30,32 +44,412 @@ impl IndexedCharsInner {
             };
         }
 
-        let mut chars = Vec::with_capacity(charlen);
-        let mut rollovers = Vec::new();
+        let mut this = Self {
+            chars: Vec::with_capacity(charlen),
+            rollovers: Vec::new(),
+        };
 
         for (char_idx, (real_idx, _)) in s.char_indices().enumerate() {
-            let mut offset_idx = real_idx - char_idx;
+            this.push_offset(char_idx, real_idx);
+        }
+
+        // ensure we did not waste memory
+        debug_assert!(this.chars.capacity() == this.chars.len());
+
+        this
+    }
+
+    /// Constructs an empty index with preallocated space for a string of `byte_len` bytes and roughly `char_count` chars.
+    pub(crate) fn with_capacity(byte_len: usize, char_count: usize) -> Self {
+        // ascii strings do not need any index space
+        if char_count >= byte_len {
+            return Self::default();
+        }
 
-            let u8_max = usize::from(u8::MAX);
+        // every rollover occurs after another u8::MAX bytes of non ascii data
+        let rollovers = (byte_len - char_count) / usize::from(u8::MAX);
 
-            offset_idx -= rollovers.len() * u8_max;
+        Self {
+            chars: Vec::with_capacity(char_count),
+            rollovers: Vec::with_capacity(rollovers),
+        }
+    }
 
-            if offset_idx > u8_max {
-                rollovers.push(char_idx);
-                offset_idx -= u8_max;
+    /// Reserves index space for at least `additional` more chars to be pushed to the index of `buf`.
+    pub(crate) fn reserve(&mut self, buf: &str, additional: usize) {
+        // in the ascii niche the offsets of existing chars are materialized on the first non ascii push
+        self.chars
+            .reserve(self.char_count(buf) + additional - self.chars.len());
+    }
+
+    /// Adds the offset of the next char to the index, given its char index and its byte index
+    fn push_offset(&mut self, char_idx: usize, real_idx: usize) {
+        let mut offset_idx = real_idx - char_idx;
+
+        let u8_max = usize::from(u8::MAX);
+
+        offset_idx -= self.rollovers.len() * u8_max;
+
+        if offset_idx > u8_max {
+            self.rollovers.push(char_idx);
+            offset_idx -= u8_max;
+
+            debug_assert!(offset_idx <= u8_max);
+        }
+
+        // unwrap safe as chars cannot grow by more than 255 bytes at once
+        // and we just checked if it was over 255 bytes, conditionally subtracting
+        self.chars.push(offset_idx.try_into().unwrap());
+    }
 
-                debug_assert!(offset_idx <= u8_max);
+    /// Extends the index with a char that was appended to the backing string.
+    ///
+    /// `char_idx` and `real_idx` are the char count and byte length of the backing string before the char was appended,
+    /// this preserves the ascii niche until the first non ascii char is pushed.
+    pub(crate) fn push(&mut self, char_idx: usize, real_idx: usize, c: char) {
+        if self.is_ascii() {
+            if c.is_ascii() {
+                return;
             }
 
-            // unwrap safe as chars cannot grow by more than 255 bytes at once
-            // and we just checked if it was over 255 bytes, conditionally subtracting
-            chars.push(offset_idx.try_into().unwrap());
+            // all prior chars were ascii and so have an offset of 0
+            self.chars.resize(char_idx, 0);
         }
 
-        // ensure we did not waste memory
-        debug_assert!(chars.capacity() == chars.len());
+        self.push_offset(char_idx, real_idx);
+    }
+
+    /// Extends the index with a string that was appended to the backing string,
+    /// `buf` must be the backing string from before `s` was appended.
+    pub(crate) fn push_str(&mut self, buf: &str, s: &str) {
+        // nothing to record if we stay in the ascii niche
+        if self.is_ascii() && s.is_ascii() {
+            return;
+        }
+
+        let char_idx = self.char_count(buf);
+
+        for (i, (real_idx, c)) in s.char_indices().enumerate() {
+            self.push(char_idx + i, buf.len() + real_idx, c);
+        }
+    }
+
+    /// Extends the index with another indexed string that was appended to the backing string,
+    /// `buf` must be the backing string from before `with` was appended, and `with_index` is the index of `with`.
+    ///
+    /// This reuses the offsets of `with_index`, so `with` does not need to be scanned.
+    pub(crate) fn append(&mut self, buf: &str, with: &str, with_index: &Self) {
+        // nothing to record if we stay in the ascii niche
+        if self.is_ascii() && with_index.is_ascii() {
+            return;
+        }
+
+        let char_idx = self.char_count(buf);
+
+        if self.is_ascii() {
+            // all prior chars were ascii and so have an offset of 0
+            self.chars.resize(char_idx, 0);
+        }
+
+        for (i, real_idx) in with_index.offsets_from(with, 0).enumerate() {
+            self.push_offset(char_idx + i, buf.len() + real_idx);
+        }
+    }
+
+    /// Truncates the index to `char_idx` chars, after the backing string was truncated to become `buf`.
+    pub(crate) fn truncate(&mut self, buf: &str, char_idx: usize) {
+        if !self.is_ascii() {
+            self.chars.truncate(char_idx);
+
+            let rollovers = self.rollovers.partition_point(|&r| r < char_idx);
+            self.rollovers.truncate(rollovers);
+        }
+
+        self.restore_ascii_niche(buf.len());
+    }
+
+    /// Splits the index at `char_idx`, after the backing string was split into `left` and `right` at that char.
+    /// `self` is truncated to index `left`, and the index of `right` is returned,
+    /// reusing the existing offsets rather than scanning `right` for char boundaries.
+    pub(crate) fn split_off(&mut self, left: &str, right: &str, char_idx: usize) -> Self {
+        if self.is_ascii() {
+            return Self::default();
+        }
+
+        let mut split = Self {
+            chars: Vec::with_capacity(self.chars.len() - char_idx),
+            rollovers: Vec::new(),
+        };
+
+        // the amount of rollovers that apply to the current index, as in byte_offset
+        let mut rollover = self.rollovers.partition_point(|&r| r < char_idx);
+
+        for idx in char_idx..self.chars.len() {
+            if self.rollovers.get(rollover) == Some(&idx) {
+                rollover += 1;
+            }
+
+            let real_idx = idx + usize::from(self.chars[idx]) + usize::from(u8::MAX) * rollover;
+
+            split.push_offset(idx - char_idx, real_idx - left.len());
+        }
+
+        split.restore_ascii_niche(right.len());
+        self.truncate(left, char_idx);
+
+        split
+    }
+
+    /// Builds the index of `&buf[bytes]`, where `bytes` spans the chars in `chars`,
+    /// the string passed must be the one this index was created with.
+    ///
+    /// The offsets of the sliced chars are rebased in a single pass rather than scanning the slice of `buf`.
+    pub(crate) fn slice(&self, buf: &str, chars: Range<usize>, bytes: Range<usize>) -> Self {
+        if self.is_ascii() {
+            return Self::default();
+        }
+
+        let mut slice = Self {
+            chars: Vec::with_capacity(chars.len()),
+            rollovers: Vec::new(),
+        };
+
+        for (i, real_idx) in self
+            .offsets_from(buf, chars.start)
+            .take(chars.len())
+            .enumerate()
+        {
+            slice.push_offset(i, real_idx - bytes.start);
+        }
+
+        slice.restore_ascii_niche(bytes.len());
+
+        slice
+    }
+
+    /// Iterates the byte offsets of every char from the char index `from` onwards, without decoding `buf`,
+    /// the string passed must be the one this index was created with.
+    pub(crate) fn offsets_from<'a>(
+        &'a self,
+        buf: &str,
+        from: usize,
+    ) -> impl ExactSizeIterator<Item = usize> + 'a {
+        // the amount of rollovers that apply to the current index, as in byte_offset
+        let mut rollover = self.rollovers.partition_point(|&r| r < from);
+
+        (from..self.char_count(buf)).map(move |idx| {
+            if self.is_ascii() {
+                return idx;
+            }
+
+            if self.rollovers.get(rollover) == Some(&idx) {
+                rollover += 1;
+            }
+
+            idx + usize::from(self.chars[idx]) + usize::from(u8::MAX) * rollover
+        })
+    }
+
+    /// Builds the index of `buf` after the chars in `chars` (spanning the bytes in `bytes`) were replaced with `with`,
+    /// where `with_index` is the index of `with`.
+    ///
+    /// This reuses the offsets of the unchanged parts of `buf` and of `with`, so no part of the new string needs to be scanned.
+    pub(crate) fn splice(
+        &self,
+        buf: &str,
+        chars: Range<usize>,
+        bytes: Range<usize>,
+        with: &str,
+        with_index: &Self,
+    ) -> Self {
+        // splicing ascii into ascii keeps the niche, with no offsets to compute
+        if self.is_ascii() && with_index.is_ascii() {
+            return Self::default();
+        }
 
-        Self { chars, rollovers }
+        let with_count = with_index.char_count(with);
+        let count = self.char_count(buf) - chars.len() + with_count;
+
+        let mut out = Self {
+            chars: Vec::with_capacity(count),
+            rollovers: Vec::new(),
+        };
+
+        if self.is_ascii() {
+            out.chars.resize(chars.start, 0);
+        } else {
+            out.chars.extend_from_slice(&self.chars[..chars.start]);
+
+            let rollovers = self.rollovers.partition_point(|&r| r < chars.start);
+            out.rollovers
+                .extend_from_slice(&self.rollovers[..rollovers]);
+        }
+
+        for (i, real_idx) in with_index.offsets_from(with, 0).enumerate() {
+            out.push_offset(chars.start + i, bytes.start + real_idx);
+        }
+
+        let tail_char = chars.start + with_count;
+        let tail_byte = bytes.start + with.len();
+
+        for (i, real_idx) in self.offsets_from(buf, chars.end).enumerate() {
+            out.push_offset(tail_char + i, tail_byte + (real_idx - bytes.end));
+        }
+
+        out.restore_ascii_niche(buf.len() - bytes.len() + with.len());
+
+        out
+    }
+
+    /// Recomputes the index from the byte offset `byte` onwards, after the backing string was modified to become `buf`.
+    ///
+    /// Everything in `buf` before `byte` must be unchanged from the string this index was built for,
+    /// so that all index entries before it remain valid and only the rest of `buf` needs to be scanned.
+    /// If this does not hold the index will not match `buf`, which
+    /// [`OwnedIndexedChars::from_raw_parts`](crate::OwnedIndexedChars::from_raw_parts) will detect.
+    ///
+    /// # Panics
+    /// Panics if `byte` is not a char boundary of `buf`, or is past its end.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añb"));
+    /// let (mut buf, mut index) = s.into_parts();
+    ///
+    /// // edit everything after "añ"
+    /// buf.truncate(3);
+    /// buf.push_str("💯c");
+    /// index.rebuild_from(&buf, 3);
+    ///
+    /// let s = OwnedIndexedChars::from_raw_parts(buf, index).unwrap();
+    /// assert_eq!(s.get_char(3), Some('c'));
+    /// ```
+    pub fn rebuild_from(&mut self, buf: &str, byte: usize) {
+        let tail = &buf[byte..];
+
+        let char_idx = if self.is_ascii() {
+            // nothing to record if we stay in the ascii niche
+            if tail.is_ascii() {
+                return;
+            }
+
+            byte
+        } else {
+            let char_idx = self.chars_before(byte);
+            self.truncate(&buf[..byte], char_idx);
+            char_idx
+        };
+
+        for (i, (real_idx, c)) in tail.char_indices().enumerate() {
+            self.push(char_idx + i, byte + real_idx, c);
+        }
+
+        self.restore_ascii_niche(buf.len());
+    }
+
+    /// Drops all offsets if the backing string of `byte_len` bytes has become ascii, so the ascii niche applies to it again
+    pub(crate) fn restore_ascii_niche(&mut self, byte_len: usize) {
+        // every char is at least a byte, so if there are as many chars as bytes they are all ascii
+        // the index is replaced rather than cleared so that its memory is freed
+        if self.chars.len() == byte_len {
+            self.chars = Vec::new();
+            self.rollovers = Vec::new();
+        }
+    }
+
+    /// Checks whether this index was built for `buf`, by checking that every char offset resolves to the correct
+    /// location in `buf`. This is O(n), but does not allocate.
+    pub(crate) fn matches(&self, buf: &str) -> bool {
+        if self.is_ascii() {
+            return buf.is_ascii() && self.rollovers.is_empty();
+        }
+
+        let mut count = 0;
+
+        for (char_idx, (real_idx, _)) in buf.char_indices().enumerate() {
+            if self.byte_offset(buf, char_idx) != Some(real_idx) {
+                return false;
+            }
+
+            count += 1;
+        }
+
+        // a rollover at or past the end would corrupt the offsets of chars pushed later
+        count == self.chars.len()
+            && self
+                .rollovers
+                .last()
+                .map_or(true, |&r| r < self.chars.len())
+    }
+
+    /// Serializes this index into a snapshot, as read by [`from_snapshot`][IndexedCharsInner::from_snapshot].
+    ///
+    /// The layout is the [`SNAPSHOT_VERSION`] byte, then the number of offsets as a little endian u64 followed by the offsets,
+    /// then the number of rollovers as a little endian u64 followed by each rollover as a little endian u64.
+    pub(crate) fn to_snapshot(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(17 + self.chars.len() + self.rollovers.len() * 8);
+
+        out.push(SNAPSHOT_VERSION);
+        out.extend_from_slice(&(self.chars.len() as u64).to_le_bytes());
+        out.extend_from_slice(&self.chars);
+        out.extend_from_slice(&(self.rollovers.len() as u64).to_le_bytes());
+
+        for &r in &self.rollovers {
+            out.extend_from_slice(&(r as u64).to_le_bytes());
+        }
+
+        out
+    }
+
+    /// Deserializes an index from a snapshot written by [`to_snapshot`][IndexedCharsInner::to_snapshot].
+    ///
+    /// This only checks that the snapshot is well formed, not that it matches any string.
+    pub(crate) fn from_snapshot(snapshot: &[u8]) -> Result<Self, IndexSnapshotErrorKind> {
+        /// Splits a little endian u64 length or index off the front of `bytes`
+        fn take_usize(bytes: &mut &[u8]) -> Result<usize, IndexSnapshotErrorKind> {
+            if bytes.len() < 8 {
+                return Err(IndexSnapshotErrorKind::Malformed);
+            }
+
+            let (head, tail) = bytes.split_at(8);
+            *bytes = tail;
+
+            let mut le = [0; 8];
+            le.copy_from_slice(head);
+
+            usize::try_from(u64::from_le_bytes(le)).map_err(|_| IndexSnapshotErrorKind::Malformed)
+        }
+
+        let (&version, mut rest) = snapshot
+            .split_first()
+            .ok_or(IndexSnapshotErrorKind::Malformed)?;
+
+        if version != SNAPSHOT_VERSION {
+            return Err(IndexSnapshotErrorKind::UnsupportedVersion(version));
+        }
+
+        let chars_len = take_usize(&mut rest)?;
+
+        if rest.len() < chars_len {
+            return Err(IndexSnapshotErrorKind::Malformed);
+        }
+
+        let (chars, mut rest) = rest.split_at(chars_len);
+        let rollovers_len = take_usize(&mut rest)?;
+
+        if rest.len() / 8 != rollovers_len || rest.len() % 8 != 0 {
+            return Err(IndexSnapshotErrorKind::Malformed);
+        }
+
+        let rollovers = (0..rollovers_len)
+            .map(|_| take_usize(&mut rest))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            chars: chars.to_vec(),
+            rollovers,
+        })
     }
 
     /// Returns whether the string for this index contains only ascii characters.
@@ -64,6 +458,74 @@ impl IndexedCharsInner {
         self.chars.is_empty()
     }
 
+    /// Returns the amount of heap memory this index has allocated in bytes, including spare capacity.
+    pub(crate) fn memory_footprint(&self) -> usize {
+        self.chars.capacity() + self.rollovers.capacity() * core::mem::size_of::<usize>()
+    }
+
+    /// Returns the amount of spare capacity this index has allocated in bytes.
+    #[cfg(test)]
+    pub(crate) fn spare_capacity(&self) -> usize {
+        self.memory_footprint()
+            - self.chars.len()
+            - self.rollovers.len() * core::mem::size_of::<usize>()
+    }
+
+    /// Shrinks the capacity of the index to fit its contents.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.chars.shrink_to_fit();
+        self.rollovers.shrink_to_fit();
+    }
+
+    /// Shrinks the capacity of the index of `buf` towards what a string of `min_bytes` bytes would need,
+    /// scaled by the current ratio of indexed chars to bytes; never below its contents.
+    pub(crate) fn shrink_to(&mut self, buf: &str, min_bytes: usize) {
+        if buf.is_empty() || self.chars.is_empty() {
+            self.shrink_to_fit();
+            return;
+        }
+
+        let ratio = |len: usize| min_bytes.saturating_mul(len) / buf.len();
+
+        self.chars.shrink_to(ratio(self.chars.len()));
+        self.rollovers.shrink_to(ratio(self.rollovers.len()));
+    }
+
+    /// Returns the number of rollovers that have occurred in this index.
+    pub(crate) fn rollover_count(&self) -> usize {
+        self.rollovers.len()
+    }
+
+    /// Returns the number of steps the rollover binary search takes for a lookup at the farthest index,
+    /// this is 0 when no rollovers have occurred.
+    pub(crate) fn max_lookup_steps(&self) -> usize {
+        // a binary search over n items takes floor(log2(n)) + 1 steps
+        (usize::BITS - self.rollovers.len().leading_zeros()) as usize
+    }
+
+    /// Finds the block of chars between rollovers that contains the char at `index`,
+    /// the string passed must be the one this index was created with.
+    /// Returns `None` if the index is out of range.
+    pub(crate) fn block_of_char(&self, buf: &str, index: usize) -> Option<BlockInfo> {
+        let count = self.char_count(buf);
+
+        if index >= count {
+            return None;
+        }
+
+        let rollover = self.rollovers.partition_point(|&r| r <= index);
+
+        let start = rollover
+            .checked_sub(1)
+            .map_or(0, |prev| self.rollovers[prev]);
+        let end = self.rollovers.get(rollover).copied().unwrap_or(count);
+
+        Some(BlockInfo {
+            bytes: self.char_boundary(buf, start)?..self.char_boundary(buf, end)?,
+            chars: start..end,
+        })
+    }
+
     /// Computes the amount of chars in the given string in O(1) time,
     /// the string passed must be the one this index was created with.
     pub(crate) fn char_count(&self, buf: &str) -> usize {
@@ -74,17 +536,22 @@ impl IndexedCharsInner {
         }
     }
 
-    /// Gets a char from a string using the index, the string passed must be the one this index was created with
-    pub(crate) fn get_char(&self, buf: &str, index: usize) -> Option<char> {
+    /// Gets the byte offset of a char in a string using the index, the string passed must be the one this index was created with.
+    /// Returns `None` if the index is out of range.
+    pub(crate) fn byte_offset(&self, buf: &str, index: usize) -> Option<usize> {
         // niche on empty chars (ascii optimization)
         if self.is_ascii() {
-            // explicitly unwrap in chars because a None indicates a bug on our end.
-            // if buf.get fails, that is a userland None.
-            return buf.get(index..).map(|c| c.chars().next().unwrap());
+            return (index < buf.len()).then(|| index);
         }
 
         // if its in self.chars we can assume its in buf
-        let mut offset = usize::from(*self.chars.get(index)?);
+        (index < self.chars.len()).then(|| self.offset(index))
+    }
+
+    /// Computes the byte offset of a char from its offset and the rollovers,
+    /// panics if the index is not in `self.chars`.
+    fn offset(&self, index: usize) -> usize {
+        let mut offset = usize::from(self.chars[index]);
 
         offset += usize::from(u8::MAX)
             * self
@@ -94,8 +561,138 @@ impl IndexedCharsInner {
                 // index where it "would" be regardless if its found, never its actual location
                 .map_or_else(|e| e, |t| t + 1);
 
+        index + offset
+    }
+
+    /// Binary searches for the amount of chars that start before the byte offset `byte`,
+    /// this only consults `self.chars` and so must not be used in the ascii niche.
+    fn chars_before(&self, byte: usize) -> usize {
+        // a char never starts before its own index so we can bound the search by byte
+        let (mut lo, mut hi) = (0, self.chars.len().min(byte));
+
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+
+            if self.offset(mid) < byte {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+
+        lo
+    }
+
+    /// Gets the byte offset of a char boundary in a string using the index, the string passed must be the one this index was created with.
+    /// Unlike [`byte_offset`][Self::byte_offset], an index equal to the char count is valid and returns the length of the string.
+    pub(crate) fn char_boundary(&self, buf: &str, index: usize) -> Option<usize> {
+        if index == self.char_count(buf) {
+            Some(buf.len())
+        } else {
+            self.byte_offset(buf, index)
+        }
+    }
+
+    /// Resolves sorted, non overlapping ranges of char indices into ranges of byte offsets in a single forward sweep,
+    /// the string passed must be the one this index was created with.
+    /// Ranges that are out of range resolve to `None`.
+    ///
+    /// Every range must have its start before its end, and end before the start of the next range,
+    /// as the rollovers are walked forwards instead of searched for each bound.
+    pub(crate) fn byte_ranges_sorted(
+        &self,
+        buf: &str,
+        ranges: &[Range<usize>],
+    ) -> Vec<Option<Range<usize>>> {
+        let count = self.char_count(buf);
+
+        // the amount of rollovers that apply to the current index, as in byte_offset
+        let mut rollover = 0;
+
+        let mut boundary = |index: usize| {
+            if index == count {
+                return buf.len();
+            }
+
+            if self.is_ascii() {
+                return index;
+            }
+
+            while self.rollovers.get(rollover).map_or(false, |&r| r <= index) {
+                rollover += 1;
+            }
+
+            index + usize::from(self.chars[index]) + usize::from(u8::MAX) * rollover
+        };
+
+        ranges
+            .iter()
+            .map(|range| (range.end <= count).then(|| boundary(range.start)..boundary(range.end)))
+            .collect()
+    }
+
+    /// Resolves the bounds of a range of char indices into a `Range`,
+    /// the string passed must be the one this index was created with.
+    /// Returns `None` if either bound is out of range, or the start is past the end.
+    pub(crate) fn char_range(
+        &self,
+        buf: &str,
+        range: impl RangeBounds<usize>,
+    ) -> Option<Range<usize>> {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.checked_add(1)?,
+            Bound::Unbounded => 0,
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.checked_add(1)?,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.char_count(buf),
+        };
+
+        (start <= end && end <= self.char_count(buf)).then(|| start..end)
+    }
+
+    /// Resolves a range of char indices into a range of byte offsets using the index,
+    /// the string passed must be the one this index was created with.
+    /// Returns `None` if either bound is out of range, or the start is past the end.
+    pub(crate) fn byte_range(
+        &self,
+        buf: &str,
+        range: impl RangeBounds<usize>,
+    ) -> Option<Range<usize>> {
+        let range = self.char_range(buf, range)?;
+
+        Some(self.char_boundary(buf, range.start)?..self.char_boundary(buf, range.end)?)
+    }
+
+    /// Finds the index of the char whose bytes contain the byte offset `byte`, the string passed must be the one this index was created with.
+    /// Returns `None` if `byte` is out of range.
+    pub(crate) fn char_index_at_byte(&self, buf: &str, byte: usize) -> Option<usize> {
+        if byte >= buf.len() {
+            return None;
+        }
+
+        if self.is_ascii() {
+            return Some(byte);
+        }
+
+        // at least 1 char starts before byte + 1, as the first char always starts at byte 0
+        Some(self.chars_before(byte + 1) - 1)
+    }
+
+    /// Gets a char from a string using the index, the string passed must be the one this index was created with
+    pub(crate) fn get_char(&self, buf: &str, index: usize) -> Option<char> {
+        // in the ascii niche every byte is a char, so we can skip utf8 decoding
+        if self.is_ascii() {
+            return buf.as_bytes().get(index).map(|&b| char::from(b));
+        }
+
+        let offset = self.byte_offset(buf, index)?;
+
         // explicitly unwrap here because a None indicates a bug on our end
-        Some(buf[index + offset..].chars().next().unwrap())
+        Some(buf[offset..].chars().next().unwrap())
     }
 }
 
@@ -109,7 +706,7 @@ fn create() {
     let s_buf = "foo";
     let s = IndexedCharsInner::new(s_buf);
 
-    assert_eq!(s.char_count(&s_buf), 3);
+    assert_eq!(s.char_count(s_buf), 3);
     assert!(s.rollovers.is_empty());
     assert!(s.is_ascii());
     assert_eq!(s.get_char(s_buf, 4), None);
@@ -151,3 +748,248 @@ fn get_idx() {
         assert_eq!(index.get_char(&s, char_idx).unwrap(), c);
     }
 }
+
+#[test]
+fn byte_range() {
+    let s = "aé💯b";
+    let index = IndexedCharsInner::new(s);
+
+    assert_eq!(index.byte_range(s, ..), Some(0..s.len()));
+    assert_eq!(index.byte_range(s, 1..3), Some(1..7));
+    assert_eq!(index.byte_range(s, 1..=2), Some(1..7));
+    assert_eq!(index.byte_range(s, 4..), Some(s.len()..s.len()));
+    assert_eq!(
+        index.byte_range(s, (Bound::Included(2), Bound::Excluded(1))),
+        None
+    );
+    assert_eq!(index.byte_range(s, ..5), None);
+
+    let ascii = "abcd";
+    let index = IndexedCharsInner::new(ascii);
+
+    assert_eq!(index.byte_range(ascii, 1..3), Some(1..3));
+    assert_eq!(index.byte_range(ascii, ..=4), None);
+}
+
+#[test]
+fn push() {
+    use alloc::string::String;
+
+    for s in [
+        "",
+        "foo",
+        "💯",
+        "a💯b",
+        &"é".repeat(1000),
+        &"aé💯".repeat(300),
+    ] {
+        let mut index = IndexedCharsInner::default();
+        let mut buf = String::new();
+
+        for c in s.chars() {
+            index.push(buf.chars().count(), buf.len(), c);
+            buf.push(c);
+        }
+
+        let fresh = IndexedCharsInner::new(s);
+
+        assert_eq!(index.chars, fresh.chars);
+        assert_eq!(index.rollovers, fresh.rollovers);
+    }
+}
+
+#[test]
+fn char_index_at_byte() {
+    use alloc::string::String;
+
+    let s: String = (0..20_000)
+        .map(|i| char::from_u32(i * 7 % 20_000).unwrap())
+        .collect();
+
+    let index = IndexedCharsInner::new(&s);
+
+    for (char_idx, (real_idx, c)) in s.char_indices().enumerate() {
+        for byte in real_idx..real_idx + c.len_utf8() {
+            assert_eq!(index.char_index_at_byte(&s, byte), Some(char_idx));
+        }
+    }
+
+    assert_eq!(index.char_index_at_byte(&s, s.len()), None);
+    assert_eq!(
+        IndexedCharsInner::new("ab").char_index_at_byte("ab", 1),
+        Some(1)
+    );
+}
+
+#[test]
+fn matches() {
+    let s = "aé💯".repeat(300);
+    let index = IndexedCharsInner::new(&s);
+
+    assert!(index.matches(&s));
+    assert!(!index.matches(&s[..s.len() - 4]));
+    assert!(!index.matches(&"é".repeat(900)));
+    assert!(!index.matches("abc"));
+
+    assert!(IndexedCharsInner::default().matches("abc"));
+    assert!(!IndexedCharsInner::default().matches("é"));
+
+    let mut phantom = IndexedCharsInner::new(&s);
+    phantom.rollovers.push(phantom.chars.len());
+    assert!(!phantom.matches(&s));
+}
+
+#[test]
+fn max_lookup_steps() {
+    assert_eq!(IndexedCharsInner::new("foo").max_lookup_steps(), 0);
+    assert_eq!(
+        IndexedCharsInner::new(&"é".repeat(200)).max_lookup_steps(),
+        0
+    );
+
+    // each rollover occurs once an offset exceeds 255 bytes of non ascii data
+    let index = IndexedCharsInner::new(&"é".repeat(257));
+    assert_eq!(index.rollovers.len(), 1);
+    assert_eq!(index.max_lookup_steps(), 1);
+
+    let index = IndexedCharsInner::new(&"💯".repeat(1000));
+    assert_eq!(index.rollovers.len(), 11);
+    assert_eq!(index.max_lookup_steps(), 4);
+}
+
+#[test]
+fn rebuild_from() {
+    use alloc::string::String;
+
+    let base = "aé💯b".repeat(200);
+
+    for byte in [0, 1, 3, 7, 400, base.len()] {
+        for tail in ["", "xyz", "é", &"💯".repeat(100)] {
+            let mut index = IndexedCharsInner::new(&base);
+
+            let mut s = String::from(&base[..byte]);
+            s.push_str(tail);
+
+            index.rebuild_from(&s, byte);
+
+            assert_eq!(index, IndexedCharsInner::new(&s));
+        }
+    }
+}
+
+#[test]
+fn ascii_niche_frees_index() {
+    let mut buf = "a".repeat(100_000) + "é";
+    let mut index = IndexedCharsInner::new(&buf);
+    assert!(index.chars.capacity() > 0);
+
+    buf.pop();
+    index.truncate(&buf, 100_000);
+    assert!(index.is_ascii());
+    assert_eq!(index.chars.capacity() + index.rollovers.capacity(), 0);
+
+    buf.insert(50_000, '💯');
+    index.rebuild_from(&buf, 50_000);
+    assert!(!index.is_ascii());
+
+    buf.remove(50_000);
+    index.rebuild_from(&buf, 50_000);
+    assert!(index.is_ascii());
+    assert_eq!(index.chars.capacity() + index.rollovers.capacity(), 0);
+}
+
+#[test]
+fn ascii_splice_allocates_nothing() {
+    let buf = "a".repeat(100_000);
+    let index = IndexedCharsInner::new(&buf);
+
+    let spliced = index.splice(&buf, 10..20, 10..20, "cd", &IndexedCharsInner::new("cd"));
+    assert!(spliced.is_ascii());
+    assert_eq!(spliced.chars.capacity() + spliced.rollovers.capacity(), 0);
+
+    let with = "ñ";
+    let spliced = index.splice(&buf, 5..5, 5..5, with, &IndexedCharsInner::new(with));
+    let mut expected = buf.clone();
+    expected.insert_str(5, with);
+    assert_eq!(spliced, IndexedCharsInner::new(&expected));
+}
+
+#[test]
+fn huge() {
+    use alloc::string::String;
+
+    // enough 4 byte chars that the rollover count and offsets exceed what u16 can store
+    let count = 6_000_000;
+    let mut s = "💯".repeat(count);
+    s.push('a');
+
+    let index = IndexedCharsInner::new(&s);
+
+    assert!(index.rollovers.len() > usize::from(u16::MAX));
+    assert!(index.byte_offset(&s, count).unwrap() > usize::from(u16::MAX) * usize::from(u8::MAX));
+
+    assert_eq!(index.char_count(&s), count + 1);
+    assert_eq!(index.get_char(&s, count), Some('a'));
+    assert_eq!(index.get_char(&s, count - 1), Some('💯'));
+    assert_eq!(index.byte_offset(&s, count), Some(s.len() - 1));
+    assert_eq!(index.char_index_at_byte(&s, s.len() - 2), Some(count - 1));
+
+    let mut tail = String::from(&s[..s.len() - 1]);
+    tail.push('b');
+    let mut rebuilt = IndexedCharsInner::new(&s);
+    rebuilt.rebuild_from(&tail, s.len() - 1);
+    assert_eq!(rebuilt.get_char(&tail, count), Some('b'));
+}
+
+#[test]
+fn ascii_niche() {
+    use alloc::string::String;
+
+    let mut buf = String::from("abc");
+    let mut index = IndexedCharsInner::new(&buf);
+    assert!(index.is_ascii());
+
+    index.push(3, 3, 'é');
+    buf.push('é');
+    assert!(!index.is_ascii());
+    assert_eq!(index.get_char(&buf, 3), Some('é'));
+
+    buf.pop();
+    index.truncate(&buf, 3);
+    assert!(index.is_ascii());
+    assert_eq!(index.get_char(&buf, 2), Some('c'));
+    assert_eq!(index.get_char(&buf, 3), None);
+
+    buf.insert(0, '💯');
+    index.rebuild_from(&buf, 0);
+    assert!(!index.is_ascii());
+
+    buf.remove(0);
+    index.rebuild_from(&buf, 0);
+    assert!(index.is_ascii());
+}
+
+#[test]
+fn block_of_char() {
+    let s = "é".repeat(600);
+    let index = IndexedCharsInner::new(&s);
+
+    assert_eq!(index.rollovers, [256, 511]);
+
+    let first = index.block_of_char(&s, 0).unwrap();
+    assert_eq!(first.chars, 0..256);
+    assert_eq!(first.bytes, 0..512);
+
+    assert_eq!(index.block_of_char(&s, 255), Some(first));
+    assert_eq!(index.block_of_char(&s, 256).unwrap().chars, 256..511);
+    assert_eq!(index.block_of_char(&s, 599).unwrap().bytes, 1022..1200);
+    assert_eq!(index.block_of_char(&s, 600), None);
+
+    assert_eq!(
+        IndexedCharsInner::new("abc")
+            .block_of_char("abc", 1)
+            .unwrap()
+            .chars,
+        0..3
+    );
+}
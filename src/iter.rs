@@ -0,0 +1,124 @@
+//! Module containing the iterator types returned by [`OwnedIndexedChars`](crate::OwnedIndexedChars)
+
+use core::str::Chars;
+
+/// Iterator over the chars of a char range along with their absolute char indices,
+/// returned by [`OwnedIndexedChars::char_indices_in_range`](crate::OwnedIndexedChars::char_indices_in_range)
+pub(crate) struct CharIndicesInRange<'a> {
+    /// Chars of the remaining range
+    pub(crate) chars: Chars<'a>,
+    /// Char index of the next char
+    pub(crate) next: usize,
+    /// Char index one past the last char of the range
+    pub(crate) end: usize,
+}
+
+impl Iterator for CharIndicesInRange<'_> {
+    type Item = (usize, char);
+
+    fn next(&mut self) -> Option<(usize, char)> {
+        let c = self.chars.next()?;
+        let index = self.next;
+        self.next += 1;
+
+        Some((index, c))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.next;
+
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for CharIndicesInRange<'_> {}
+
+/// Iterator over the byte offsets of every char boundary including the end of the string,
+/// returned by [`OwnedIndexedChars::char_boundaries`](crate::OwnedIndexedChars::char_boundaries)
+pub(crate) struct CharBoundaries<I> {
+    /// Byte offsets of the remaining chars
+    pub(crate) offsets: I,
+    /// Byte length of the string, taken once all chars have been yielded
+    pub(crate) byte_len: Option<usize>,
+}
+
+impl<I: ExactSizeIterator<Item = usize>> Iterator for CharBoundaries<I> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        self.offsets.next().or_else(|| self.byte_len.take())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.offsets.len() + usize::from(self.byte_len.is_some());
+
+        (len, Some(len))
+    }
+}
+
+impl<I: ExactSizeIterator<Item = usize>> ExactSizeIterator for CharBoundaries<I> {}
+
+/// Asserts that an iterator reports its exact remaining length at every step
+#[cfg(test)]
+fn assert_exact_len(mut iter: impl ExactSizeIterator) {
+    let mut len = iter.len();
+
+    while iter.next().is_some() {
+        assert_eq!(iter.size_hint(), (len - 1, Some(len - 1)));
+        len -= 1;
+        assert_eq!(iter.len(), len);
+    }
+
+    assert_eq!(len, 0);
+}
+
+#[test]
+fn exact_len() {
+    use crate::OwnedIndexedChars;
+    use alloc::{string::String, vec::Vec};
+
+    for s in ["", "abc", "añ💯b", &"aé💯b".repeat(200)] {
+        let s = OwnedIndexedChars::new(String::from(s));
+        let count = s.char_count();
+
+        for (start, end) in [
+            (0, count),
+            (0, 0),
+            (count / 2, count),
+            (count / 3, count / 2),
+        ] {
+            assert_eq!(s.char_indices_in_range(start..end).len(), end - start);
+            assert_exact_len(s.char_indices_in_range(start..end));
+        }
+
+        for k in [1, 2, 3, 7, 256, count + 1] {
+            let expected: String = (0..count)
+                .step_by(k)
+                .map(|i| s.get_char(i).unwrap())
+                .collect();
+
+            assert_eq!(s.chars_step_by(k).len(), expected.chars().count());
+            assert_eq!(s.chars_step_by(k).collect::<String>(), expected);
+            assert_exact_len(s.chars_step_by(k));
+        }
+
+        for k in [1, 2, 3, 7, count.max(1), count + 1] {
+            let expected: Vec<String> = s
+                .chars()
+                .collect::<Vec<_>>()
+                .windows(k)
+                .map(|w| w.iter().collect())
+                .collect();
+
+            assert_eq!(s.char_window_ranges(k).len(), count.saturating_sub(k - 1));
+            assert_exact_len(s.char_window_ranges(k));
+
+            for (range, window) in s.char_window_ranges(k).zip(&expected) {
+                assert_eq!(s.get_char_range(range), Some(window.as_str()));
+            }
+        }
+
+        assert_eq!(s.char_boundaries().len(), count + 1);
+        assert_exact_len(s.char_boundaries());
+    }
+}
@@ -23,15 +23,49 @@
 //! In this way, we achieve what behaves as an O(1) char lookup (technically worst case O(log n)) for most strings, while saving memory over `Vec<char>` (sans the case where the string is only made up of 4 byte characters, which acts as the worst case for time complexity too).
 //!
 //! Additionally, as a niche optimization, if the string contains only ascii (all offsets 0); it will simply not allocate any extra memory, and gain perfect O(1) lookup.
+//! Mutations keep track of this, so a string that becomes ascii again after an edit regains the niche.
+//!
+//! # Features
+//! - `std`: Enables APIs that depend on the standard library, such as `HashMap` based char frequencies.
+//! - `unicode-case`: Enables Unicode case insensitive comparison by simple case folding with `OwnedIndexedChars::eq_ignore_case`.
+//! - `unicode-width`: Enables approximate terminal display widths with `OwnedIndexedChars::display_width`.
 //!
 
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
 mod indexed_chars;
-use indexed_chars::IndexedCharsInner;
+pub use indexed_chars::IndexedCharsInner;
 
 mod borrowed;
+mod boxed;
+mod builder;
+#[cfg(feature = "unicode-case")]
+mod case;
+mod cursor;
+mod diff;
+mod error;
+mod iter;
+mod lines;
 mod owned;
+mod shared;
+mod stats;
+mod utf16;
+#[cfg(feature = "unicode-width")]
+mod width;
+
+pub mod prelude;
 
 pub use borrowed::IndexedChars;
+pub use boxed::BoxedIndexedChars;
+pub use builder::IndexBuilder;
+pub use cursor::CharCursor;
+pub use diff::CharEdit;
+pub use error::{IndexSnapshotError, IndexSnapshotErrorKind, RawPartsError};
+pub use lines::LineIndexedChars;
 pub use owned::OwnedIndexedChars;
+pub use shared::SharedIndexedChars;
+pub use stats::{BlockInfo, IndexStats};
+pub use utf16::Utf16View;
@@ -0,0 +1,255 @@
+//! Module containing [`LineIndexedChars`], an indexed string that also tracks its lines
+
+use alloc::{string::String, vec::Vec};
+use core::{fmt, ops::Deref, ops::Range};
+
+use crate::OwnedIndexedChars;
+
+/// An [`OwnedIndexedChars`] that additionally indexes the char positions of its newlines.
+///
+/// This allows [`line_count`][LineIndexedChars::line_count] in O(1) and [`line_char_range`][LineIndexedChars::line_char_range]
+/// in O(1) average time, at the cost of one `usize` per `'\n'` in the string. Lines are split on `'\n'` like [`str::split`],
+/// so a trailing newline is followed by an empty line, and an empty string has a single empty line.
+///
+/// The mutation methods of this type keep both indexes up to date, all read only methods of [`OwnedIndexedChars`] are available through [`Deref`].
+pub struct LineIndexedChars {
+    /// Backing indexed string
+    text: OwnedIndexedChars,
+    /// Char indices of every `'\n'` in the string, in ascending order
+    newlines: Vec<usize>,
+}
+
+impl LineIndexedChars {
+    /// Constructs a new [`LineIndexedChars`] from an indexed string, scanning it once for newlines.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::{LineIndexedChars, OwnedIndexedChars};
+    /// let s = LineIndexedChars::new(OwnedIndexedChars::new(String::from("añ\nb")));
+    ///
+    /// assert_eq!(s.line_count(), 2);
+    /// # assert_eq!(s.line_char_range(1), Some(3..4));
+    /// ```
+    #[must_use]
+    pub fn new(text: OwnedIndexedChars) -> Self {
+        let newlines = text
+            .chars()
+            .enumerate()
+            .filter(|&(_, c)| c == '\n')
+            .map(|(i, _)| i)
+            .collect();
+
+        Self { text, newlines }
+    }
+
+    /// Returns the number of lines in the string, this is always at least 1.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::{LineIndexedChars, OwnedIndexedChars};
+    /// let s = LineIndexedChars::new(OwnedIndexedChars::new(String::from("a\nb\n")));
+    ///
+    /// assert_eq!(s.line_count(), 3);
+    /// # assert_eq!(LineIndexedChars::new(OwnedIndexedChars::new(String::new())).line_count(), 1);
+    /// ```
+    #[must_use]
+    pub fn line_count(&self) -> usize {
+        self.newlines.len() + 1
+    }
+
+    /// Returns the char range of the zero based `line`, excluding its terminating newline,
+    /// or `None` if the line does not exist.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::{LineIndexedChars, OwnedIndexedChars};
+    /// let s = LineIndexedChars::new(OwnedIndexedChars::new(String::from("añ\nb\n")));
+    ///
+    /// assert_eq!(s.line_char_range(0), Some(0..2));
+    /// assert_eq!(s.line_char_range(1), Some(3..4));
+    /// assert_eq!(s.line_char_range(2), Some(5..5));
+    /// assert_eq!(s.line_char_range(3), None);
+    /// ```
+    #[must_use]
+    pub fn line_char_range(&self, line: usize) -> Option<Range<usize>> {
+        if line > self.newlines.len() {
+            return None;
+        }
+
+        let start = line
+            .checked_sub(1)
+            .map_or(0, |prev| self.newlines[prev] + 1);
+        let end = self
+            .newlines
+            .get(line)
+            .copied()
+            .unwrap_or_else(|| self.text.char_count());
+
+        Some(start..end)
+    }
+
+    /// Appends a char to the end of the string, extending both indexes.
+    pub fn push(&mut self, c: char) {
+        if c == '\n' {
+            self.newlines.push(self.text.char_count());
+        }
+
+        self.text.push(c);
+    }
+
+    /// Appends a string slice to the end of the string, extending both indexes.
+    pub fn push_str(&mut self, s: &str) {
+        let start = self.text.char_count();
+
+        self.newlines.extend(
+            s.chars()
+                .enumerate()
+                .filter(|&(_, c)| c == '\n')
+                .map(|(i, _)| start + i),
+        );
+
+        self.text.push_str(s);
+    }
+
+    /// Removes the last char from the string and returns it, or `None` if it is empty.
+    pub fn pop(&mut self) -> Option<char> {
+        let c = self.text.pop()?;
+
+        if c == '\n' {
+            self.newlines.pop();
+        }
+
+        Some(c)
+    }
+
+    /// Inserts a char at the char index `index`, shifting all chars and lines after it.
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than [`char_count`][OwnedIndexedChars::char_count].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::{LineIndexedChars, OwnedIndexedChars};
+    /// let mut s = LineIndexedChars::new(OwnedIndexedChars::new(String::from("añb")));
+    /// s.insert(2, '\n');
+    ///
+    /// assert_eq!(s.line_count(), 2);
+    /// assert_eq!(s.line_char_range(1), Some(3..4));
+    /// ```
+    pub fn insert(&mut self, index: usize, c: char) {
+        self.text.insert(index, c);
+
+        let split = self.newlines.partition_point(|&n| n < index);
+
+        for n in &mut self.newlines[split..] {
+            *n += 1;
+        }
+
+        if c == '\n' {
+            self.newlines.insert(split, index);
+        }
+    }
+
+    /// Removes the char at the char index `index` and returns it, shifting all chars and lines after it.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of range.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::{LineIndexedChars, OwnedIndexedChars};
+    /// let mut s = LineIndexedChars::new(OwnedIndexedChars::new(String::from("a\nñ\nb")));
+    ///
+    /// assert_eq!(s.remove(1), '\n');
+    /// assert_eq!(s.line_count(), 2);
+    /// assert_eq!(s.line_char_range(0), Some(0..2));
+    /// ```
+    pub fn remove(&mut self, index: usize) -> char {
+        let c = self.text.remove(index);
+
+        let split = self.newlines.partition_point(|&n| n < index);
+
+        if c == '\n' {
+            self.newlines.remove(split);
+        }
+
+        for n in &mut self.newlines[split..] {
+            *n -= 1;
+        }
+
+        c
+    }
+
+    /// Returns a reference to the underlying [`OwnedIndexedChars`].
+    #[must_use]
+    pub fn as_indexed(&self) -> &OwnedIndexedChars {
+        &self.text
+    }
+
+    /// Drops the newline index and returns the underlying [`OwnedIndexedChars`].
+    #[must_use]
+    pub fn into_indexed(self) -> OwnedIndexedChars {
+        self.text
+    }
+}
+
+impl From<OwnedIndexedChars> for LineIndexedChars {
+    fn from(text: OwnedIndexedChars) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<String> for LineIndexedChars {
+    fn from(s: String) -> Self {
+        Self::new(OwnedIndexedChars::new(s))
+    }
+}
+
+impl Deref for LineIndexedChars {
+    type Target = OwnedIndexedChars;
+
+    fn deref(&self) -> &OwnedIndexedChars {
+        &self.text
+    }
+}
+
+impl fmt::Debug for LineIndexedChars {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        <OwnedIndexedChars as fmt::Debug>::fmt(&self.text, f)
+    }
+}
+
+impl fmt::Display for LineIndexedChars {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        <OwnedIndexedChars as fmt::Display>::fmt(&self.text, f)
+    }
+}
+
+#[test]
+fn newlines_after_mutations() {
+    let mut s = LineIndexedChars::from("aé\n💯\n".repeat(100));
+
+    let check = |s: &LineIndexedChars| {
+        let fresh = LineIndexedChars::from(String::from(s.as_str()));
+        assert_eq!(s.newlines, fresh.newlines);
+    };
+
+    check(&s);
+    s.push('\n');
+    check(&s);
+    s.push_str("x\ny\n\n");
+    check(&s);
+    s.insert(0, '\n');
+    check(&s);
+    s.insert(3, 'ñ');
+    check(&s);
+    assert_eq!(s.remove(4), '\n');
+    check(&s);
+    assert_eq!(s.remove(0), '\n');
+    check(&s);
+
+    while s.pop().is_some() {
+        check(&s);
+        assert_eq!(s.line_count(), s.split('\n').count());
+    }
+}
@@ -1,15 +1,19 @@
 //! Module containing [`OwnedIndexedChars`] and its trait implementations
 
-use alloc::string::String;
+use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
 use core::{
     borrow::Borrow,
     cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd},
     fmt,
     hash::{Hash, Hasher},
-    ops::Deref,
+    ops::{Bound, Deref, Range, RangeBounds},
 };
 
-use crate::IndexedCharsInner;
+use crate::{
+    iter::{CharBoundaries, CharIndicesInRange},
+    BlockInfo, BoxedIndexedChars, CharCursor, CharEdit, IndexSnapshotError, IndexSnapshotErrorKind,
+    IndexStats, IndexedCharsInner, RawPartsError, SharedIndexedChars, Utf16View,
+};
 
 /// A string whose char indices have been cached for ~O(1) char lookup. Owned variant.
 ///
@@ -22,11 +26,14 @@ use crate::IndexedCharsInner;
 /// The internal representation of this type allows for up to 255 bytes of non ascii unicode chars before an internal rollover occurs (thus tending the complexity towards O(log n)), this is the tradeoff made to reduce memory usage. See the section [`How it Works`](index.html#how-it-works) for details on why char indexing worst case is O(log n), and why in practical cases it appears to be O(1).
 ///
 /// This type mimics a `String` with its trait impls, including `Debug`, `Display`, `PartialEq` with `&str` `PartialOrd` with `&str`, `Hash`, and `AsRef`/`Borrow`.
+/// With the `std` feature enabled, it also implements `AsRef<OsStr>` and `AsRef<Path>` so it may be passed directly to filesystem APIs.
 pub struct OwnedIndexedChars {
     /// Backing string allocation
     buf: String,
     /// Char offsets index
     inner: IndexedCharsInner,
+    /// Counter bumped by every mutation, see [`generation`][OwnedIndexedChars::generation]
+    generation: u64,
 }
 
 impl OwnedIndexedChars {
@@ -45,7 +52,250 @@ impl OwnedIndexedChars {
     pub fn new(s: String) -> Self {
         let inner = IndexedCharsInner::new(&s);
 
-        Self { buf: s, inner }
+        Self::from_inner(s, inner)
+    }
+
+    /// Constructs a new empty [`OwnedIndexedChars`] with space preallocated for `byte_len` bytes of string data,
+    /// and an index sized for an estimated `est_char_count` chars.
+    ///
+    /// This avoids reallocations when building a string of known size with [`push`][OwnedIndexedChars::push] and
+    /// [`push_str`][OwnedIndexedChars::push_str]. When `est_char_count` is at least `byte_len` the text is assumed to be
+    /// ascii, and no index space is allocated.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut s = OwnedIndexedChars::with_capacity_for(2000, 1000);
+    ///
+    /// for _ in 0..1000 {
+    ///     s.push('é');
+    /// }
+    ///
+    /// assert_eq!(s.char_count(), 1000);
+    /// # assert!(s.as_string().capacity() >= 2000);
+    /// ```
+    #[must_use]
+    pub fn with_capacity_for(byte_len: usize, est_char_count: usize) -> Self {
+        Self::from_inner(
+            String::with_capacity(byte_len),
+            IndexedCharsInner::with_capacity(byte_len, est_char_count),
+        )
+    }
+
+    /// Constructs a new [`OwnedIndexedChars`] from a slice of chars, encoding each char and indexing it in the same pass.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let chars = vec!['a', 'ñ', '💯'];
+    /// let s = OwnedIndexedChars::from_chars(&chars);
+    ///
+    /// assert_eq!(s, *"añ💯");
+    /// assert_eq!(s.chars().collect::<Vec<_>>(), chars);
+    /// ```
+    #[must_use]
+    pub fn from_chars(chars: &[char]) -> Self {
+        let mut s = Self::from_inner(String::new(), IndexedCharsInner::default());
+        s.extend_from_chars(chars);
+        s
+    }
+
+    /// Constructs a new [`OwnedIndexedChars`] from possibly invalid utf8, replacing invalid sequences with
+    /// [`U+FFFD REPLACEMENT CHARACTER`][char::REPLACEMENT_CHARACTER] like [`String::from_utf8_lossy`].
+    ///
+    /// Each valid run of `bytes` is indexed as it is copied into the string, so the decoded string is never scanned again.
+    ///
+    /// # Panics
+    /// Never panics on any input, a panic indicates a bug in this crate.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::from_utf8_lossy(b"a\xff\xc3\xb1");
+    ///
+    /// assert_eq!(s, *"a\u{FFFD}ñ");
+    /// assert_eq!(s.get_char(1), Some(char::REPLACEMENT_CHARACTER));
+    /// assert_eq!(s.get_char(2), Some('ñ'));
+    /// ```
+    #[must_use]
+    pub fn from_utf8_lossy(bytes: &[u8]) -> Self {
+        let mut s = Self::from_inner(
+            String::with_capacity(bytes.len()),
+            IndexedCharsInner::default(),
+        );
+        let mut rest = bytes;
+
+        loop {
+            match core::str::from_utf8(rest) {
+                Ok(valid) => {
+                    s.push_str(valid);
+                    return s;
+                }
+                Err(e) => {
+                    let (valid, invalid) = rest.split_at(e.valid_up_to());
+
+                    // explicitly unwrap here because the bytes before valid_up_to are guaranteed to be valid utf8,
+                    // an error indicates a bug on our end
+                    s.push_str(core::str::from_utf8(valid).unwrap());
+                    s.push(char::REPLACEMENT_CHARACTER);
+
+                    rest = match e.error_len() {
+                        Some(len) => &invalid[len..],
+                        None => &[],
+                    };
+                }
+            }
+        }
+    }
+
+    /// Reads utf8 text from `reader` until it is exhausted, indexing each chunk as it arrives with an [`IndexBuilder`](crate::IndexBuilder).
+    ///
+    /// Chars that are split across two reads are carried over and indexed once the rest of their bytes arrive,
+    /// so the reader may return chunks of any size.
+    ///
+    /// # Errors
+    /// Returns any error produced by `reader` other than [`Interrupted`](std::io::ErrorKind::Interrupted), which is retried.
+    /// Returns an error of kind [`InvalidData`](std::io::ErrorKind::InvalidData) if the text is not valid utf8, including if it ends in the middle of a char.
+    ///
+    /// # Panics
+    /// Never panics on any input, a panic indicates a bug in this crate.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::from_reader("añ💯".as_bytes()).unwrap();
+    ///
+    /// assert_eq!(s.get_char(2), Some('💯'));
+    /// assert!(OwnedIndexedChars::from_reader(&b"a\xff"[..]).is_err());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_reader(mut reader: impl std::io::Read) -> std::io::Result<Self> {
+        use std::io::{Error, ErrorKind};
+
+        let mut builder = crate::IndexBuilder::new();
+        let mut chunk = [0; 8192];
+        // bytes of an incomplete char carried over to the start of chunk
+        let mut pending = 0;
+
+        loop {
+            let filled = match reader.read(&mut chunk[pending..]) {
+                Ok(0) => break,
+                Ok(n) => pending + n,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+
+            let valid = match core::str::from_utf8(&chunk[..filled]) {
+                Ok(_) => filled,
+                Err(e) if e.error_len().is_none() => e.valid_up_to(),
+                Err(e) => return Err(Error::new(ErrorKind::InvalidData, e)),
+            };
+
+            // explicitly unwrap here because the bytes before valid are guaranteed to be valid utf8,
+            // an error indicates a bug on our end
+            builder.push_str(core::str::from_utf8(&chunk[..valid]).unwrap());
+
+            chunk.copy_within(valid..filled, 0);
+            pending = filled - valid;
+        }
+
+        if pending != 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "stream did not contain valid UTF-8",
+            ));
+        }
+
+        Ok(builder.finish())
+    }
+
+    /// Constructs a new [`OwnedIndexedChars`] from a string and an index that was built for that string.
+    pub(crate) fn from_inner(buf: String, inner: IndexedCharsInner) -> Self {
+        Self {
+            buf,
+            inner,
+            generation: 0,
+        }
+    }
+
+    /// Reconstructs an [`OwnedIndexedChars`] from a string and a previously built index, as returned by
+    /// [`into_parts`][OwnedIndexedChars::into_parts].
+    ///
+    /// The index is checked to match `buf` in O(n) time, this is cheaper than rebuilding it as it does not allocate.
+    ///
+    /// # Errors
+    /// Returns a [`RawPartsError`] holding both parts if the index was not built for `buf`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let (buf, index) = OwnedIndexedChars::new(String::from("añb")).into_parts();
+    ///
+    /// let s = OwnedIndexedChars::from_raw_parts(buf, index).unwrap();
+    /// assert_eq!(s.get_char(2), Some('b'));
+    ///
+    /// let (_, index) = s.into_parts();
+    /// assert!(OwnedIndexedChars::from_raw_parts(String::from("ñab"), index).is_err());
+    /// ```
+    pub fn from_raw_parts(
+        buf: String,
+        mut inner: IndexedCharsInner,
+    ) -> Result<Self, RawPartsError> {
+        if inner.matches(&buf) {
+            inner.restore_ascii_niche(buf.len());
+            Ok(Self::from_inner(buf, inner))
+        } else {
+            Err(RawPartsError { buf, inner })
+        }
+    }
+
+    /// Reconstructs an [`OwnedIndexedChars`] from a string and a previously built index without checking that they match.
+    ///
+    /// The index must have been built for exactly `buf`, as checked by [`from_raw_parts`][OwnedIndexedChars::from_raw_parts].
+    /// Breaking this invariant is not undefined behavior, but lookups may return incorrect results or panic.
+    /// The invariant is checked with a debug assertion.
+    #[must_use]
+    pub fn from_raw_parts_unchecked(buf: String, mut inner: IndexedCharsInner) -> Self {
+        debug_assert!(inner.matches(&buf), "index does not match string");
+        inner.restore_ascii_niche(buf.len());
+
+        Self::from_inner(buf, inner)
+    }
+
+    /// Reattaches an index snapshot taken with [`index_snapshot`][OwnedIndexedChars::index_snapshot] to its string.
+    ///
+    /// The snapshot is decoded and checked to match `buf` in O(n) time, like [`from_raw_parts`][OwnedIndexedChars::from_raw_parts].
+    /// A snapshot that fails these checks is never trusted: the error holds `buf`, and
+    /// [`IndexSnapshotError::rebuild`] indexes it from scratch instead, so that a stale or
+    /// corrupted snapshot can be recovered from while still being noticed.
+    ///
+    /// # Errors
+    /// Returns an [`IndexSnapshotError`] holding `buf` if the snapshot was written in an unsupported
+    /// format version, is malformed, or was not taken of `buf`, which can be told apart with [`IndexSnapshotError::kind`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::{IndexSnapshotError, IndexSnapshotErrorKind, OwnedIndexedChars};
+    /// let snapshot = OwnedIndexedChars::new(String::from("añb")).index_snapshot();
+    ///
+    /// let s = OwnedIndexedChars::attach_index(String::from("añb"), &snapshot).unwrap();
+    /// assert_eq!(s.get_char(2), Some('b'));
+    ///
+    /// let err = OwnedIndexedChars::attach_index(String::from("ñab"), &snapshot).unwrap_err();
+    /// assert_eq!(err.kind(), IndexSnapshotErrorKind::Mismatch);
+    ///
+    /// let s = err.rebuild();
+    /// assert_eq!(s.get_char(2), Some('b'));
+    /// # assert!(OwnedIndexedChars::attach_index(String::new(), &[]).map_err(IndexSnapshotError::into_string).is_err());
+    /// ```
+    pub fn attach_index(buf: String, snapshot: &[u8]) -> Result<Self, IndexSnapshotError> {
+        match IndexedCharsInner::from_snapshot(snapshot) {
+            Ok(inner) => Self::from_raw_parts(buf, inner).map_err(|e| IndexSnapshotError {
+                buf: e.buf,
+                kind: IndexSnapshotErrorKind::Mismatch,
+            }),
+            Err(kind) => Err(IndexSnapshotError { buf, kind }),
+        }
     }
 
     /// Indexes into the backing string to retrieve the nth codepoint.
@@ -64,17 +314,2378 @@ impl OwnedIndexedChars {
         self.inner.get_char(&self.buf, index)
     }
 
+    /// Retrieves the nth codepoint along with the codepoint after it, or `None` if `index` is out of range.
+    ///
+    /// The byte offset of `index` is resolved once and both chars are decoded from there, so this costs a single lookup.
+    /// The second char is `None` when `index` is the last char.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añ💯"));
+    ///
+    /// assert_eq!(s.get_char_pair(1), Some(('ñ', Some('💯'))));
+    /// assert_eq!(s.get_char_pair(2), Some(('💯', None)));
+    /// assert_eq!(s.get_char_pair(3), None);
+    /// ```
+    #[must_use]
+    pub fn get_char_pair(&self, index: usize) -> Option<(char, Option<char>)> {
+        let offset = self.inner.byte_offset(&self.buf, index)?;
+        let mut chars = self.buf[offset..].chars();
+
+        Some((chars.next()?, chars.next()))
+    }
+
+    /// Indexes into the backing string to retrieve the nth codepoint, where negative indices count from the end.
+    ///
+    /// Non negative indices behave like [`get_char`][OwnedIndexedChars::get_char], while `-1` is the last char, `-2` the second to last, and so on.
+    /// Returns `None` if the index is out of range in either direction.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añb"));
+    ///
+    /// assert_eq!(s.get_char_signed(0), Some('a'));
+    /// assert_eq!(s.get_char_signed(-1), Some('b'));
+    /// assert_eq!(s.get_char_signed(-3), Some('a'));
+    /// assert_eq!(s.get_char_signed(-4), None);
+    /// # assert_eq!(s.get_char_signed(3), None);
+    /// # assert_eq!(s.get_char_signed(isize::MIN), None);
+    /// ```
+    #[must_use]
+    pub fn get_char_signed(&self, index: isize) -> Option<char> {
+        if let Ok(index) = usize::try_from(index) {
+            self.get_char(index)
+        } else {
+            self.get_char(self.char_count().checked_sub(index.unsigned_abs())?)
+        }
+    }
+
+    /// Finds the char whose utf8 bytes contain the byte offset `byte`, returning its char index and the char itself.
+    ///
+    /// Unlike slicing, `byte` does not need to lie on a char boundary, which is useful for snapping imprecise byte offsets to chars.
+    /// Returns `None` if `byte` is out of range.
+    ///
+    /// This operation has an average case of O(log n).
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("a€b"));
+    ///
+    /// // byte 2 is in the middle of the 3 byte '€'
+    /// assert_eq!(s.char_at_byte(2), Some((1, '€')));
+    /// assert_eq!(s.char_at_byte(4), Some((2, 'b')));
+    /// assert_eq!(s.char_at_byte(5), None);
+    /// ```
+    #[must_use]
+    pub fn char_at_byte(&self, byte: usize) -> Option<(usize, char)> {
+        let index = self.inner.char_index_at_byte(&self.buf, byte)?;
+
+        self.get_char(index).map(|c| (index, c))
+    }
+
+    /// Returns the utf8 byte length (1 to 4) of the nth codepoint, or `None` if the index is out of range.
+    ///
+    /// This is computed from the offsets of the char and the one after it in the index, without decoding or slicing the string.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añ€💯"));
+    ///
+    /// assert_eq!(s.char_byte_len_at(2), Some(3));
+    /// assert_eq!(s.char_byte_len_at(4), None);
+    /// # for i in 0..5 {
+    /// #     assert_eq!(s.char_byte_len_at(i), s.get_char(i).map(|c| c.len_utf8() as u8));
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn char_byte_len_at(&self, index: usize) -> Option<u8> {
+        let start = self.inner.byte_offset(&self.buf, index)?;
+        let end = self.inner.char_boundary(&self.buf, index + 1)?;
+
+        u8::try_from(end - start).ok()
+    }
+
+    /// Checks whether the nth codepoint is equal to `c`, returning false if the index is out of range.
+    ///
+    /// This compares the utf8 bytes of the backing string directly against the encoded form of `c`,
+    /// and so avoids decoding a char entirely; for ascii this is a single byte comparison.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("føo"));
+    ///
+    /// assert!(s.char_eq_at(1, 'ø'));
+    /// assert!(!s.char_eq_at(2, 'ø'));
+    /// assert!(!s.char_eq_at(3, 'o'));
+    /// ```
+    #[must_use]
+    pub fn char_eq_at(&self, index: usize, c: char) -> bool {
+        match self.inner.byte_offset(&self.buf, index) {
+            Some(offset) => {
+                self.buf.as_bytes()[offset..].starts_with(c.encode_utf8(&mut [0; 4]).as_bytes())
+            }
+            None => false,
+        }
+    }
+
+    /// Checks whether `needle` occurs in the string starting at the char at `index`, returning false if the index is out of range
+    /// or the needle would run past the end of the string.
+    ///
+    /// This compares bytes directly after resolving the byte offset of `index`, so nothing is sliced or allocated.
+    /// An empty needle matches at every index up to and including the char count.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añ let x"));
+    ///
+    /// assert!(s.matches_at(3, "let"));
+    /// assert!(!s.matches_at(4, "let"));
+    /// assert!(!s.matches_at(7, "xy"));
+    /// ```
+    #[must_use]
+    pub fn matches_at(&self, index: usize, needle: &str) -> bool {
+        match self.inner.char_boundary(&self.buf, index) {
+            Some(offset) => self.buf.as_bytes()[offset..].starts_with(needle.as_bytes()),
+            None => false,
+        }
+    }
+
+    /// Checks whether this string has the same number of chars as `other` in O(1) time.
+    ///
+    /// Strings with differing char counts are never equal, and this check is also used by the `PartialEq` impl
+    /// to reject such strings before comparing their bytes.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let a = OwnedIndexedChars::new(String::from("ñb"));
+    /// let b = OwnedIndexedChars::new(String::from("abc"));
+    ///
+    /// assert!(!a.same_char_count(&b));
+    /// assert!(a.same_char_count(&OwnedIndexedChars::new(String::from("añ"))));
+    /// ```
+    #[must_use]
+    pub fn same_char_count(&self, other: &OwnedIndexedChars) -> bool {
+        self.char_count() == other.char_count()
+    }
+
+    /// Computes a 64 bit fingerprint of the contents of the string, suitable for keying persistent caches.
+    ///
+    /// This is the 64 bit FNV-1a hash of the utf8 bytes of the string. It depends on nothing but those bytes,
+    /// so it is independent of the layout and capacity of the index and of how the value was built, and the algorithm
+    /// is a stable part of the API that will not change between versions of this crate.
+    /// Unlike the `Hash` impl it does not depend on the [`Hasher`] used, but it is not resistant to deliberate collisions.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let a = OwnedIndexedChars::new(String::from("añb"));
+    /// let b = OwnedIndexedChars::from_chars(&['a', 'ñ', 'b']);
+    ///
+    /// assert_eq!(a.content_fingerprint(), b.content_fingerprint());
+    /// assert_eq!(OwnedIndexedChars::new(String::from("a")).content_fingerprint(), 0xaf63_dc4c_8601_ec8c);
+    /// ```
+    #[must_use]
+    pub fn content_fingerprint(&self) -> u64 {
+        /// FNV-1a 64 bit offset basis
+        const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        /// FNV-1a 64 bit prime
+        const PRIME: u64 = 0x0100_0000_01b3;
+
+        self.buf.bytes().fold(OFFSET_BASIS, |hash, b| {
+            (hash ^ u64::from(b)).wrapping_mul(PRIME)
+        })
+    }
+
+    /// Returns the generation of the string, a counter that is bumped by every method that mutates it.
+    ///
+    /// Read only methods never change the generation, so caches of data derived from the string can be invalidated
+    /// by comparing it against the generation they were built at. Mutating methods may bump the generation even if
+    /// the call ended up not changing the string, such as popping from an empty string.
+    ///
+    /// The generation is not part of the value of the string: it is ignored by comparisons and hashing. Every newly
+    /// constructed value starts over at an arbitrary generation, including values rebuilt with [`from_raw_parts`][OwnedIndexedChars::from_raw_parts]
+    /// and values returned by methods like [`split_off_chars`][OwnedIndexedChars::split_off_chars],
+    /// so generations should only be compared between states of the same value.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut s = OwnedIndexedChars::new(String::from("añ"));
+    /// let generation = s.generation();
+    ///
+    /// _ = s.get_char(1);
+    /// assert_eq!(s.generation(), generation);
+    ///
+    /// s.push('b');
+    /// assert!(s.generation() > generation);
+    /// ```
+    #[must_use]
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
     /// Returns the number of chars present in the backing string, this operation is free thanks to
-    /// how [`OwnedIndexedChars`] is constructed
+    /// how [`OwnedIndexedChars`] is constructed, and remains free after any mutation
+    #[must_use]
+    pub fn char_count(&self) -> usize {
+        self.inner.char_count(&self.buf)
+    }
+
+    /// Returns the index of the char after `index`, or `None` if `index` is the last char or is out of range.
+    ///
+    /// For an empty string there are no valid char indices, so this always returns `None`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añb"));
+    ///
+    /// assert_eq!(s.next_char_index(1), Some(2));
+    /// assert_eq!(s.next_char_index(2), None);
+    /// # assert_eq!(s.next_char_index(3), None);
+    /// # assert_eq!(OwnedIndexedChars::new(String::new()).next_char_index(0), None);
+    /// ```
+    #[must_use]
+    pub fn next_char_index(&self, index: usize) -> Option<usize> {
+        let next = index.checked_add(1)?;
+
+        (next < self.char_count()).then(|| next)
+    }
+
+    /// Returns the index of the char before `index`, or `None` if `index` is the first char or is out of range.
+    ///
+    /// For an empty string there are no valid char indices, so this always returns `None`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añb"));
+    ///
+    /// assert_eq!(s.prev_char_index(2), Some(1));
+    /// assert_eq!(s.prev_char_index(0), None);
+    /// assert_eq!(s.prev_char_index(3), None);
+    /// # assert_eq!(OwnedIndexedChars::new(String::new()).prev_char_index(0), None);
+    /// ```
+    #[must_use]
+    pub fn prev_char_index(&self, index: usize) -> Option<usize> {
+        if index < self.char_count() {
+            index.checked_sub(1)
+        } else {
+            None
+        }
+    }
+
+    /// Returns whether the string contains only ascii chars, in O(1) time.
+    ///
+    /// This shadows [`str::is_ascii`], which would scan the whole string. The index tracks this itself,
+    /// and it is kept up to date by every mutation. An empty string is considered ascii.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut s = OwnedIndexedChars::new(String::from("abc"));
+    /// assert!(s.is_ascii());
+    ///
+    /// s.push('ñ');
+    /// assert!(!s.is_ascii());
+    ///
+    /// s.pop();
+    /// assert!(s.is_ascii());
+    /// # s.insert(1, '💯');
+    /// # assert!(!s.is_ascii());
+    /// # s.remove(1);
+    /// # assert!(s.is_ascii());
+    /// ```
+    #[must_use]
+    pub fn is_ascii(&self) -> bool {
+        self.inner.is_ascii()
+    }
+
+    /// Returns whether the string contains any non ascii chars, in O(1) time.
+    ///
+    /// This is the negation of [`is_ascii`][OwnedIndexedChars::is_ascii].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// assert!(OwnedIndexedChars::new(String::from("añ")).contains_non_ascii());
+    /// assert!(!OwnedIndexedChars::new(String::from("an")).contains_non_ascii());
+    /// ```
+    #[must_use]
+    pub fn contains_non_ascii(&self) -> bool {
+        !self.is_ascii()
+    }
+
+    /// Returns the number of steps the internal rollover search takes for a lookup at the farthest char index.
+    ///
+    /// This is 0 for strings that have not rolled over (including all ascii strings), and grows logarithmically after that.
+    /// It is intended for tests and benchmarks that target the worst case lookup, and is not part of the stable API.
+    #[doc(hidden)]
+    #[must_use]
+    pub fn max_lookup_steps(&self) -> usize {
+        self.inner.max_lookup_steps()
+    }
+
+    /// Summarizes the shape and memory cost of the index of this string.
+    ///
+    /// This is intended for deciding whether indexing a given kind of input is worthwhile, see [`IndexStats`] for the meaning of each value.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let stats = OwnedIndexedChars::new(String::from("foo")).index_stats();
+    ///
+    /// assert!(stats.is_ascii);
+    /// assert_eq!(stats.char_count, 3);
+    /// assert_eq!(stats.index_overhead_bytes, 0);
+    /// # assert_eq!((stats.byte_len, stats.rollover_count, stats.max_lookup_steps), (3, 0, 0));
+    /// ```
+    #[must_use]
+    pub fn index_stats(&self) -> IndexStats {
+        IndexStats {
+            char_count: self.char_count(),
+            byte_len: self.buf.len(),
+            rollover_count: self.inner.rollover_count(),
+            is_ascii: self.inner.is_ascii(),
+            index_overhead_bytes: self.inner.memory_footprint(),
+            max_lookup_steps: self.inner.max_lookup_steps(),
+        }
+    }
+
+    /// Describes the block of the index containing the char at `index`, or `None` if the index is out of range.
+    ///
+    /// A block is a run of chars between two internal rollovers, and lookups of chars in later blocks
+    /// take more search steps. This is intended for profiling and diagnosing lookup cost.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new("é".repeat(600));
+    /// let block = s.block_of_char(300).unwrap();
+    ///
+    /// assert_eq!(block.chars, 256..511);
+    /// assert_eq!(block.bytes, 512..1022);
+    /// # assert_eq!(s.block_of_char(600), None);
+    /// ```
+    #[must_use]
+    pub fn block_of_char(&self, index: usize) -> Option<BlockInfo> {
+        self.inner.block_of_char(&self.buf, index)
+    }
+
+    /// Returns the substring covering a range of char indices, or `None` if the range is out of bounds or its start is past its end.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añ💯b"));
+    ///
+    /// assert_eq!(s.get_char_range(1..3), Some("ñ💯"));
+    /// assert_eq!(s.get_char_range(2..), Some("💯b"));
+    /// assert_eq!(s.get_char_range(..5), None);
+    /// ```
+    #[must_use]
+    pub fn get_char_range(&self, range: impl RangeBounds<usize>) -> Option<&str> {
+        let range = self.inner.byte_range(&self.buf, range)?;
+
+        Some(&self.buf[range])
+    }
+
+    /// Writes the bytes of a range of chars directly to `w`, without allocating a substring.
+    ///
+    /// # Errors
+    /// Returns an error of kind [`InvalidInput`](std::io::ErrorKind::InvalidInput) if the range is out of bounds or its start is past its end,
+    /// and otherwise returns any error produced by `w`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añ💯b"));
+    /// let mut out = Vec::new();
+    ///
+    /// s.write_char_range_to(1..3, &mut out).unwrap();
+    /// s.write_char_range_to(2..2, &mut out).unwrap();
+    /// assert_eq!(out, "ñ💯".as_bytes());
+    ///
+    /// assert!(s.write_char_range_to(3..5, &mut out).is_err());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn write_char_range_to(
+        &self,
+        range: impl RangeBounds<usize>,
+        w: &mut impl std::io::Write,
+    ) -> std::io::Result<()> {
+        let range = self.get_char_range(range).ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "char range out of bounds")
+        })?;
+
+        w.write_all(range.as_bytes())
+    }
+
+    /// Clamps an arbitrary range of char indices to `0..char_count()`, so that it may be applied without panicking.
+    ///
+    /// Both bounds are limited to the char count, and a range whose start is past its end becomes empty at its start.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añ💯bçdefgh"));
+    ///
+    /// assert_eq!(s.clamp_char_range(5..100), 5..10);
+    /// assert_eq!(s.clamp_char_range(20..), 10..10);
+    /// assert_eq!(s.clamp_char_range(..=3), 0..4);
+    /// # assert_eq!(s.clamp_char_range((core::ops::Bound::Included(6), core::ops::Bound::Excluded(2))), 6..6);
+    /// ```
+    #[must_use]
+    pub fn clamp_char_range(&self, range: impl RangeBounds<usize>) -> Range<usize> {
+        let count = self.char_count();
+
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        }
+        .min(count);
+
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.saturating_add(1),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => count,
+        }
+        .clamp(start, count);
+
+        start..end
+    }
+
+    /// Checks whether a range of char indices is within `0..char_count()` and its start is not past its end,
+    /// as required by methods such as [`get_char_range`][OwnedIndexedChars::get_char_range].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añ💯"));
+    ///
+    /// assert!(s.char_range_in_bounds(1..3));
+    /// assert!(s.char_range_in_bounds(3..));
+    /// assert!(!s.char_range_in_bounds(2..4));
+    /// ```
+    #[must_use]
+    pub fn char_range_in_bounds(&self, range: impl RangeBounds<usize>) -> bool {
+        self.inner.char_range(&self.buf, range).is_some()
+    }
+
+    /// Resolves many ranges of char indices into ranges of byte offsets at once, ranges that are out of bounds or have their start past their end resolve to `None`.
+    ///
+    /// When the ranges are sorted and non overlapping, as the tokens of a highlighter usually are, they are all resolved
+    /// in a single forward sweep over the index. Otherwise each range is resolved on its own.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("let ñ = \"💯\";"));
+    ///
+    /// assert_eq!(
+    ///     s.byte_ranges_of_char_ranges(&[0..3, 4..5, 8..11, 12..20]),
+    ///     [Some(0..3), Some(4..6), Some(9..15), None]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn byte_ranges_of_char_ranges(&self, ranges: &[Range<usize>]) -> Vec<Option<Range<usize>>> {
+        let sorted = ranges.iter().all(|r| r.start <= r.end)
+            && ranges.windows(2).all(|w| w[0].end <= w[1].start);
+
+        if sorted {
+            self.inner.byte_ranges_sorted(&self.buf, ranges)
+        } else {
+            ranges
+                .iter()
+                .map(|r| self.inner.byte_range(&self.buf, r.clone()))
+                .collect()
+        }
+    }
+
+    /// Finds the first occurrence of `needle` with [`str::find`], returning the char range it covers.
+    ///
+    /// Both ends of the match are converted from byte offsets to char indices through the index,
+    /// so the result may be passed directly to [`get_char_range`][OwnedIndexedChars::get_char_range].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añ💯ñ💯"));
+    /// let range = s.find_str_char_range("ñ💯").unwrap();
+    ///
+    /// assert_eq!(range, 1..3);
+    /// assert_eq!(s.get_char_range(range), Some("ñ💯"));
+    /// assert_eq!(s.find_str_char_range("x"), None);
+    /// ```
+    #[must_use]
+    pub fn find_str_char_range(&self, needle: &str) -> Option<Range<usize>> {
+        let start = self.buf.find(needle)?;
+        let end = start + needle.len();
+
+        // the end of the string is a boundary but not the start of a char
+        let char_index = |byte| {
+            if byte == self.buf.len() {
+                Some(self.char_count())
+            } else {
+                self.inner.char_index_at_byte(&self.buf, byte)
+            }
+        };
+
+        Some(char_index(start)?..char_index(end)?)
+    }
+
+    /// Copies a range of chars into a new [`OwnedIndexedChars`], building an index for just that range.
+    ///
+    /// This is useful when the range must be detached from `self` and owned independently.
+    /// If a borrow of the range is sufficient, indexing a slice of [`as_str`][OwnedIndexedChars::as_str]
+    /// with an [`IndexedChars`](crate::IndexedChars) avoids copying the string data.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds, or its start is past its end.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("abcd"));
+    /// let sub = s.subindex(1..3);
+    ///
+    /// assert_eq!(sub, *"bc");
+    /// assert_eq!(sub.get_char(1), Some('c'));
+    /// ```
+    #[must_use]
+    pub fn subindex(&self, range: impl RangeBounds<usize>) -> OwnedIndexedChars {
+        let range = self
+            .inner
+            .byte_range(&self.buf, range)
+            .expect("char range out of bounds");
+
+        Self::new(String::from(&self.buf[range]))
+    }
+
+    /// Copies a range of chars into a new [`OwnedIndexedChars`], or returns `None` if the range is out of bounds or its start is past its end.
+    ///
+    /// The index of the copy is built from the corresponding span of this index, so the copied chars are not scanned again.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añ💯bç"));
+    /// let slice = s.slice_to_owned(1..4).unwrap();
+    ///
+    /// assert_eq!(slice, *"ñ💯b");
+    /// assert_eq!(slice.get_char(0), s.get_char(1));
+    /// assert!(s.slice_to_owned(3..6).is_none());
+    /// ```
+    #[must_use]
+    pub fn slice_to_owned(&self, range: impl RangeBounds<usize>) -> Option<OwnedIndexedChars> {
+        let chars = self.inner.char_range(&self.buf, range)?;
+        let bytes = self.inner.byte_range(&self.buf, chars.clone())?;
+
+        let inner = self.inner.slice(&self.buf, chars, bytes.clone());
+
+        Some(Self::from_inner(String::from(&self.buf[bytes]), inner))
+    }
+
+    /// Returns a [`CharCursor`] positioned at the start of the string.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añ"));
+    ///
+    /// assert_eq!(s.cursor().collect::<String>(), "añ");
+    /// ```
+    #[must_use]
+    pub fn cursor(&self) -> CharCursor<'_> {
+        self.cursor_at(0)
+            .unwrap_or_else(|| unreachable!("0 is always a char boundary"))
+    }
+
+    /// Returns a [`CharCursor`] positioned at the char `index`, or `None` if `index` is greater than the char count.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añ💯"));
+    ///
+    /// assert_eq!(s.cursor_at(1).unwrap().remaining(), "ñ💯");
+    /// assert!(s.cursor_at(3).is_some());
+    /// assert!(s.cursor_at(4).is_none());
+    /// ```
+    #[must_use]
+    pub fn cursor_at(&self, index: usize) -> Option<CharCursor<'_>> {
+        CharCursor::new(&self.buf, &self.inner, index)
+    }
+
+    /// Returns a [`Utf16View`] of this string, for converting between char indices and utf16 offsets.
+    ///
+    /// Constructing the view walks the string once, it should be kept around for as long as the string is not mutated.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("a💯b"));
+    ///
+    /// assert_eq!(s.utf16_view().utf16_len(), 4);
+    /// ```
+    #[must_use]
+    pub fn utf16_view(&self) -> Utf16View<'_> {
+        Utf16View::new(self)
+    }
+
+    /// Iterates the chars of a char range along with their absolute char indices.
+    ///
+    /// The start of the range is found through the index, after which the chars are walked in order.
+    /// The returned iterator knows its exact length.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds, or its start is past its end.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añbç"));
+    /// let iter = s.char_indices_in_range(1..3);
+    ///
+    /// assert_eq!(iter.len(), 2);
+    /// assert_eq!(iter.collect::<Vec<_>>(), [(1, 'ñ'), (2, 'b')]);
+    /// ```
+    pub fn char_indices_in_range(
+        &self,
+        range: impl RangeBounds<usize>,
+    ) -> impl ExactSizeIterator<Item = (usize, char)> + '_ {
+        let range = self
+            .inner
+            .char_range(&self.buf, range)
+            .expect("char range out of bounds");
+
+        let bytes = self
+            .inner
+            .byte_range(&self.buf, range.clone())
+            .expect("char range out of bounds");
+
+        CharIndicesInRange {
+            chars: self.buf[bytes].chars(),
+            next: range.start,
+            end: range.end,
+        }
+    }
+
+    /// Copies the utf8 bytes of a range of chars into `out` without allocating.
+    ///
+    /// Returns the number of bytes written on success.
+    /// Only whole chars are ever copied.
+    ///
+    /// # Errors
+    /// Returns the number of bytes required if `out` is too small, in which case `out` is left untouched.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds, or its start is past its end.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añb"));
+    /// let mut out = [0; 4];
+    ///
+    /// assert_eq!(s.copy_char_range_into(1..3, &mut out), Ok(3));
+    /// assert_eq!(&out[..3], "ñb".as_bytes());
+    ///
+    /// assert_eq!(s.copy_char_range_into(.., &mut out[..2]), Err(4));
+    /// ```
+    pub fn copy_char_range_into(
+        &self,
+        range: impl RangeBounds<usize>,
+        out: &mut [u8],
+    ) -> Result<usize, usize> {
+        let range = self
+            .inner
+            .byte_range(&self.buf, range)
+            .expect("char range out of bounds");
+
+        let bytes = &self.buf.as_bytes()[range];
+
+        match out.get_mut(..bytes.len()) {
+            Some(out) => {
+                out.copy_from_slice(bytes);
+                Ok(bytes.len())
+            }
+            None => Err(bytes.len()),
+        }
+    }
+
+    /// Returns the number of leading chars that `self` and `other` have in common.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let a = OwnedIndexedChars::new(String::from("naïve"));
+    /// let b = OwnedIndexedChars::new(String::from("naïf"));
+    ///
+    /// assert_eq!(a.common_char_prefix_len(&b), 3);
+    /// # assert_eq!(a.common_char_prefix_len(&a), 5);
+    /// # assert_eq!(a.common_char_prefix_len(&OwnedIndexedChars::new(String::from("naí"))), 2);
+    /// ```
+    #[must_use]
+    pub fn common_char_prefix_len(&self, other: &OwnedIndexedChars) -> usize {
+        self.common_prefix_len_with_str(&other.buf)
+    }
+
+    /// Returns the number of leading chars that `self` and `other` have in common, in chars rather than bytes.
+    ///
+    /// This is the char count of the part of `other` that matches, for example to highlight the matched part of a completion.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añb💯"));
+    ///
+    /// assert_eq!(s.common_prefix_len_with_str("añc"), 2);
+    /// assert_eq!(s.common_prefix_len_with_str("añb💯 and more"), 4);
+    /// # assert_eq!(s.common_prefix_len_with_str(""), 0);
+    /// ```
+    #[must_use]
+    pub fn common_prefix_len_with_str(&self, other: &str) -> usize {
+        self.buf
+            .chars()
+            .zip(other.chars())
+            .take_while(|(a, b)| a == b)
+            .count()
+    }
+
+    /// Returns the number of trailing chars that `self` and `other` have in common.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let a = OwnedIndexedChars::new(String::from("señor"));
+    /// let b = OwnedIndexedChars::new(String::from("monsieur"));
+    ///
+    /// assert_eq!(a.common_char_suffix_len(&b), 1);
+    /// # assert_eq!(OwnedIndexedChars::new(String::from("añob")).common_char_suffix_len(&OwnedIndexedChars::new(String::from("ob"))), 2);
+    /// ```
+    #[must_use]
+    pub fn common_char_suffix_len(&self, other: &OwnedIndexedChars) -> usize {
+        self.buf
+            .chars()
+            .rev()
+            .zip(other.buf.chars().rev())
+            .take_while(|(a, b)| a == b)
+            .count()
+    }
+
+    /// Computes a char level diff from this string to `other`, as a shortest sequence of [`CharEdit`]s.
+    ///
+    /// The common prefix and suffix of both strings are trimmed first, then the differing middle parts are
+    /// diffed with Myers' algorithm. This takes time proportional to the length of the middle parts times the number of
+    /// inserted and deleted chars, and memory linear in the length of the middle parts, so it is fast for similar strings.
+    ///
+    /// # Panics
+    /// Never panics on any input, a panic indicates a bug in this crate.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::{CharEdit, OwnedIndexedChars};
+    /// let old = OwnedIndexedChars::new(String::from("añb"));
+    /// let new = OwnedIndexedChars::new(String::from("a💯b"));
+    ///
+    /// assert_eq!(
+    ///     old.char_diff(&new),
+    ///     [
+    ///         CharEdit::Keep('a'),
+    ///         CharEdit::Delete('ñ'),
+    ///         CharEdit::Insert('💯'),
+    ///         CharEdit::Keep('b'),
+    ///     ]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn char_diff(&self, other: &OwnedIndexedChars) -> Vec<CharEdit> {
+        let (count, other_count) = (self.char_count(), other.char_count());
+
+        let prefix = self.common_char_prefix_len(other);
+        // the suffix must not overlap the prefix in the shorter string
+        let suffix = self
+            .common_char_suffix_len(other)
+            .min(count.min(other_count) - prefix);
+
+        let middle = |s: &OwnedIndexedChars, count: usize| -> Vec<char> {
+            s.get_char_range(prefix..count - suffix)
+                .expect("prefix and suffix are within the char count")
+                .chars()
+                .collect()
+        };
+
+        let mut edits = Vec::with_capacity(count.max(other_count));
+
+        edits.extend(self.buf.chars().take(prefix).map(CharEdit::Keep));
+        crate::diff::myers_diff(
+            &middle(self, count),
+            &middle(other, other_count),
+            &mut edits,
+        );
+        edits.extend(
+            self.get_char_range(count - suffix..)
+                .expect("prefix and suffix are within the char count")
+                .chars()
+                .map(CharEdit::Keep),
+        );
+
+        edits
+    }
+
+    /// Returns the smallest char (by scalar value) in the backing string, or `None` if it is empty.
+    ///
+    /// This is a linear scan of the string, and is not accelerated by the index.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("hello"));
+    ///
+    /// assert_eq!(s.min_char(), Some('e'));
+    /// # assert_eq!(OwnedIndexedChars::new(String::new()).min_char(), None);
+    /// ```
+    #[must_use]
+    pub fn min_char(&self) -> Option<char> {
+        self.buf.chars().min()
+    }
+
+    /// Returns the largest char (by scalar value) in the backing string, or `None` if it is empty.
+    ///
+    /// This is a linear scan of the string, and is not accelerated by the index.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("héllo"));
+    ///
+    /// assert_eq!(s.max_char(), Some('é'));
+    /// # assert_eq!(OwnedIndexedChars::new(String::new()).max_char(), None);
+    /// ```
+    #[must_use]
+    pub fn max_char(&self) -> Option<char> {
+        self.buf.chars().max()
+    }
+
+    /// Checks whether the chars of the string are in non decreasing order, comparing adjacent chars in a single scan.
+    ///
+    /// Empty and single char strings are always sorted.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// assert!(OwnedIndexedChars::new(String::from("aabñ💯")).chars_are_sorted());
+    /// assert!(!OwnedIndexedChars::new(String::from("añb")).chars_are_sorted());
+    /// # assert!(OwnedIndexedChars::new(String::new()).chars_are_sorted());
+    /// # assert!(OwnedIndexedChars::new(String::from("ñ")).chars_are_sorted());
+    /// ```
+    #[must_use]
+    pub fn chars_are_sorted(&self) -> bool {
+        self.buf
+            .chars()
+            .zip(self.buf.chars().skip(1))
+            .all(|(a, b)| a <= b)
+    }
+
+    /// Binary searches the chars of the string for `target`, like [`slice::binary_search`].
+    ///
+    /// The chars must be in non decreasing order, as checked by [`chars_are_sorted`][OwnedIndexedChars::chars_are_sorted],
+    /// otherwise the result is unspecified. Every probe is a char lookup through the index.
+    ///
+    /// # Errors
+    /// Returns the char index `target` could be inserted at to keep the chars sorted, if it is not found.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("acñ💯"));
+    ///
+    /// assert_eq!(s.binary_search_char('ñ'), Ok(2));
+    /// assert_eq!(s.binary_search_char('b'), Err(1));
+    /// assert_eq!(s.binary_search_char('\u{10FFFF}'), Err(4));
+    /// ```
+    pub fn binary_search_char(&self, target: char) -> Result<usize, usize> {
+        let (mut lo, mut hi) = (0, self.char_count());
+
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+
+            match self.get_char(mid).map(|c| c.cmp(&target)) {
+                Some(Ordering::Less) => lo = mid + 1,
+                Some(Ordering::Equal) => return Ok(mid),
+                _ => hi = mid,
+            }
+        }
+
+        Err(lo)
+    }
+
+    /// Returns the uppercase equivalent of this string as a new [`OwnedIndexedChars`].
+    ///
+    /// This behaves like [`str::to_uppercase`], but builds the index while the converted chars are collected,
+    /// so the result does not need to be walked a second time.
+    /// Note that uppercasing may change both the char count and the byte length of the string.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("straße"));
+    /// let upper = s.to_uppercase_indexed();
+    ///
+    /// assert_eq!(upper, *"STRASSE");
+    /// assert_eq!(upper.char_count(), 7);
+    /// assert_eq!(upper.get_char(5), Some('S'));
+    /// ```
+    #[must_use]
+    pub fn to_uppercase_indexed(&self) -> OwnedIndexedChars {
+        Self::from_chars_iter(
+            self.buf.chars().flat_map(char::to_uppercase),
+            self.buf.len(),
+        )
+    }
+
+    /// Returns the lowercase equivalent of this string as a new [`OwnedIndexedChars`].
+    ///
+    /// This behaves like [`str::to_lowercase`], but builds the index while the converted chars are collected,
+    /// so the result does not need to be walked a second time.
+    /// Note that lowercasing may change both the char count and the byte length of the string.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("İSTANBUL"));
+    /// let lower = s.to_lowercase_indexed();
+    ///
+    /// assert_eq!(lower, *"i̇stanbul");
+    /// assert_eq!(lower.get_char(2), Some('s'));
+    /// # assert_eq!(OwnedIndexedChars::new(String::from("ΟΔΟΣ")).to_lowercase_indexed(), *"οδος");
+    /// ```
+    #[must_use]
+    pub fn to_lowercase_indexed(&self) -> OwnedIndexedChars {
+        // final sigma lowercases depending on its surrounding context, which str::to_lowercase handles for us
+        if self.buf.contains('Σ') {
+            return Self::new(self.buf.to_lowercase());
+        }
+
+        Self::from_chars_iter(
+            self.buf.chars().flat_map(char::to_lowercase),
+            self.buf.len(),
+        )
+    }
+
+    /// Returns a new [`OwnedIndexedChars`] with the chars of this string in reverse order.
+    ///
+    /// Chars are reversed as whole codepoints (not bytes), and the index is built during the reversal.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añ💯"));
+    /// let rev = s.reversed();
+    ///
+    /// assert_eq!(rev, *"💯ña");
+    /// assert_eq!(rev.get_char(1), Some('ñ'));
+    /// assert_eq!(rev.reversed(), s);
+    /// ```
+    #[must_use]
+    pub fn reversed(&self) -> OwnedIndexedChars {
+        Self::from_chars_iter(self.buf.chars().rev(), self.buf.len())
+    }
+
+    /// Builds a new [`OwnedIndexedChars`] from an iterator of chars, indexing each char as it is collected.
+    /// `capacity` is the initial byte capacity of the backing string.
+    fn from_chars_iter(chars: impl Iterator<Item = char>, capacity: usize) -> Self {
+        let mut buf = String::with_capacity(capacity);
+        let mut inner = IndexedCharsInner::default();
+
+        for (char_idx, c) in chars.enumerate() {
+            inner.push(char_idx, buf.len(), c);
+            buf.push(c);
+        }
+
+        Self::from_inner(buf, inner)
+    }
+
+    /// Returns the char index of the start of each line, in a single O(n) pass.
+    ///
+    /// The first line always starts at 0, and a new line starts after every `\n`,
+    /// so a trailing newline produces a final empty line starting at [`char_count`][OwnedIndexedChars::char_count].
+    ///
+    /// The returned table can be binary searched to convert many char indices into lines.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añ\nb\n"));
+    /// let lines = s.line_start_offsets();
+    ///
+    /// assert_eq!(lines, [0, 3, 5]);
+    /// // the line containing char 4
+    /// assert_eq!(lines.binary_search(&4).unwrap_or_else(|e| e - 1), 1);
+    /// ```
+    #[must_use]
+    pub fn line_start_offsets(&self) -> Vec<usize> {
+        let mut lines = vec![0];
+
+        lines.extend(
+            self.buf
+                .chars()
+                .enumerate()
+                .filter(|&(_, c)| c == '\n')
+                .map(|(i, _)| i + 1),
+        );
+
+        lines
+    }
+
+    /// Converts a char index into a zero based `(line, column)` pair, where the column is counted in chars.
+    ///
+    /// `line_starts` must be the table returned by [`line_start_offsets`][OwnedIndexedChars::line_start_offsets]
+    /// for the current string, which is binary searched to find the line in O(log n) time.
+    /// The table is taken as an argument rather than stored, so that strings that are never queried by line do not pay
+    /// for keeping one up to date through every edit. Build it once and reuse it for as many conversions as needed
+    /// until the string is next modified, a table of another string or one made stale by an edit gives incorrect results.
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than [`char_count`][OwnedIndexedChars::char_count], or if `line_starts`
+    /// does not start with line 0 at char index 0 or has a line starting past the end of the string.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añ\nbç"));
+    /// let lines = s.line_start_offsets();
+    ///
+    /// assert_eq!(s.char_index_to_line_col(&lines, 1), (0, 1));
+    /// assert_eq!(s.char_index_to_line_col(&lines, 3), (1, 0));
+    /// assert_eq!(s.char_index_to_line_col(&lines, 5), (1, 2));
+    /// ```
+    #[must_use]
+    pub fn char_index_to_line_col(&self, line_starts: &[usize], index: usize) -> (usize, usize) {
+        let count = self.char_count();
+
+        assert!(index <= count, "char index out of bounds");
+        assert!(
+            line_starts.first() == Some(&0),
+            "line start table does not start at char index 0"
+        );
+        assert!(
+            line_starts.last().map_or(false, |&last| last <= count),
+            "line start table has lines past the end of the string"
+        );
+
+        // the first line starts at 0, so at least one line start is not past index
+        let line = line_starts.partition_point(|&start| start <= index) - 1;
+
+        (line, index - line_starts[line])
+    }
+
+    /// Maps every char along with its char index to a value, collecting the results in a single pass.
+    ///
+    /// The returned `Vec` is preallocated to [`char_count`][OwnedIndexedChars::char_count] elements,
+    /// and the chars are walked in order rather than looked up by index.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añb"));
+    /// let widths = s.map_chars(|i, c| (i, c.len_utf8()));
+    ///
+    /// assert_eq!(widths, [(0, 1), (1, 2), (2, 1)]);
+    /// assert_eq!(widths.len(), s.char_count());
+    /// ```
+    pub fn map_chars<T>(&self, mut f: impl FnMut(usize, char) -> T) -> Vec<T> {
+        let mut out = Vec::with_capacity(self.char_count());
+
+        out.extend(self.buf.chars().enumerate().map(|(i, c)| f(i, c)));
+
+        out
+    }
+
+    /// Decodes every char of the string into a `Vec<char>` in a single forward pass.
+    ///
+    /// The returned `Vec` is preallocated to [`char_count`][OwnedIndexedChars::char_count] elements.
+    /// For algorithms that perform many random accesses a cached `Vec<char>` may beat [`get_char`][OwnedIndexedChars::get_char],
+    /// at the cost of 4 bytes of memory per char.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añ💯"));
+    /// let chars = s.to_char_vec();
+    ///
+    /// assert_eq!(chars, ['a', 'ñ', '💯']);
+    /// assert_eq!(chars, s.chars().collect::<Vec<_>>());
+    /// # assert_eq!(chars.len(), s.char_count());
+    /// ```
+    #[must_use]
+    pub fn to_char_vec(&self) -> Vec<char> {
+        let mut out = Vec::with_capacity(self.char_count());
+
+        out.extend(self.buf.chars());
+
+        out
+    }
+
+    /// Returns the char index of the nth (zero based) occurrence of `needle`, or `None` if there are not that many occurrences.
+    ///
+    /// This is found in a single forward scan, counting chars as it goes.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("a,ñ,b,ç"));
+    ///
+    /// assert_eq!(s.nth_char_match(',', 2), Some(5));
+    /// assert_eq!(s.nth_char_match(',', 3), None);
+    /// assert_eq!(s.nth_char_match('ç', 0), Some(6));
+    /// ```
+    #[must_use]
+    pub fn nth_char_match(&self, needle: char, n: usize) -> Option<usize> {
+        self.buf
+            .chars()
+            .enumerate()
+            .filter(|&(_, c)| c == needle)
+            .nth(n)
+            .map(|(i, _)| i)
+    }
+
+    /// Splits the string at the nth (zero based, like [`nth_char_match`][OwnedIndexedChars::nth_char_match]) occurrence of `delim`,
+    /// or returns `None` if there are not that many occurrences.
+    ///
+    /// Like [`split_at_char`][OwnedIndexedChars::split_at_char] the second part starts at the split point, so it begins with the delimiter.
+    /// The occurrence is found in a single forward scan, without a separate lookup of the split point.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("/home/ñu/file"));
+    ///
+    /// assert_eq!(s.split_at_nth_char_match('/', 1), Some(("/home", "/ñu/file")));
+    /// assert_eq!(s.split_at_nth_char_match('/', 0), Some(("", "/home/ñu/file")));
+    /// assert_eq!(s.split_at_nth_char_match('/', 2), Some(("/home/ñu", "/file")));
+    /// assert_eq!(s.split_at_nth_char_match('/', 3), None);
+    /// ```
+    #[must_use]
+    pub fn split_at_nth_char_match(&self, delim: char, n: usize) -> Option<(&str, &str)> {
+        let (byte, _) = self
+            .buf
+            .char_indices()
+            .filter(|&(_, c)| c == delim)
+            .nth(n)?;
+
+        Some(self.buf.split_at(byte))
+    }
+
+    /// Iterates the char index just past each occurrence of `needle`, that is the index of each match plus one.
+    ///
+    /// Char indices are tracked with a running count as the string is walked, so no lookups are needed per match.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("ñañ"));
+    /// let ends: Vec<_> = s.char_match_end_indices('ñ').collect();
+    ///
+    /// assert_eq!(ends, [1, 3]);
+    /// assert_eq!(ends.last(), Some(&s.char_count()));
+    /// ```
+    pub fn char_match_end_indices(&self, needle: char) -> impl Iterator<Item = usize> + '_ {
+        self.buf
+            .chars()
+            .enumerate()
+            .filter(move |&(_, c)| c == needle)
+            .map(|(i, _)| i + 1)
+    }
+
+    /// Splits the string on the last occurrence of `delim`, returning the parts before and after it along with the char index of the delimiter.
+    ///
+    /// The string is searched from the end, and the char index of the match is then found through the index.
+    /// Returns `None` if `delim` does not occur in the string.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añ.tar.gz"));
+    /// assert_eq!(s.rsplit_once_char('.'), Some(("añ.tar", "gz", 6)));
+    ///
+    /// let s = OwnedIndexedChars::new(String::from("ñ/"));
+    /// assert_eq!(s.rsplit_once_char('/'), Some(("ñ", "", 1)));
+    /// assert_eq!(s.rsplit_once_char('.'), None);
+    /// ```
+    #[must_use]
+    pub fn rsplit_once_char(&self, delim: char) -> Option<(&str, &str, usize)> {
+        let byte = self.buf.rfind(delim)?;
+        let index = self.inner.char_index_at_byte(&self.buf, byte)?;
+
+        Some((
+            &self.buf[..byte],
+            &self.buf[byte + delim.len_utf8()..],
+            index,
+        ))
+    }
+
+    /// Splits the string by whitespace like [`str::split_whitespace`], yielding each token along with the char index it starts at.
+    ///
+    /// Char indices are tracked with a running count as the string is walked, so no lookups are needed per token.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("  añ\u{3000}b  ç "));
+    /// let tokens: Vec<_> = s.split_whitespace_indexed().collect();
+    ///
+    /// assert_eq!(tokens, [(2, "añ"), (5, "b"), (8, "ç")]);
+    /// ```
+    pub fn split_whitespace_indexed(&self) -> impl Iterator<Item = (usize, &str)> + '_ {
+        let mut rest = self.buf.as_str();
+        let mut char_idx = 0;
+
+        core::iter::from_fn(move || {
+            let trimmed = rest.trim_start();
+            char_idx += rest[..rest.len() - trimmed.len()].chars().count();
+
+            if trimmed.is_empty() {
+                return None;
+            }
+
+            let end = trimmed.find(char::is_whitespace).unwrap_or(trimmed.len());
+            let (token, tail) = trimmed.split_at(end);
+
+            let start = char_idx;
+            char_idx += token.chars().count();
+            rest = tail;
+
+            Some((start, token))
+        })
+    }
+
+    /// Splits the string on `term` like [`str::split_terminator`], yielding each piece along with the char index it starts at.
+    ///
+    /// A trailing `term` does not produce a final empty piece, every other empty piece is kept.
+    /// Char indices are tracked with a running count as the string is walked, so no lookups are needed per piece.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añ;;ç;"));
+    /// let pieces: Vec<_> = s.split_terminator_char_indexed(';').collect();
+    ///
+    /// assert_eq!(pieces, [(0, "añ"), (3, ""), (4, "ç")]);
+    /// ```
+    pub fn split_terminator_char_indexed(
+        &self,
+        term: char,
+    ) -> impl Iterator<Item = (usize, &str)> + '_ {
+        let mut char_idx = 0;
+
+        self.buf.split_terminator(term).map(move |piece| {
+            let start = char_idx;
+            char_idx += piece.chars().count() + 1;
+
+            (start, piece)
+        })
+    }
+
+    /// Splits the string into consecutive chunks of at most `max_bytes` bytes each, never splitting a char.
+    ///
+    /// Each chunk is made as long as possible, ending on the last char boundary that fits in the limit.
+    /// A single char that is wider than `max_bytes` is yielded alone as its own chunk, so every chunk is non empty
+    /// and the iterator always makes progress.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("abñ💯c"));
+    ///
+    /// let chunks: Vec<_> = s.chunks_by_bytes(3).collect();
+    /// assert_eq!(chunks, ["ab", "ñ", "💯", "c"]);
+    ///
+    /// let chunks: Vec<_> = s.chunks_by_bytes(6).collect();
+    /// assert_eq!(chunks, ["abñ", "💯c"]);
+    /// ```
+    pub fn chunks_by_bytes(&self, max_bytes: usize) -> impl Iterator<Item = &str> + '_ {
+        let mut rest = self.buf.as_str();
+
+        core::iter::from_fn(move || {
+            let first = rest.chars().next()?;
+
+            let mut end = max_bytes.min(rest.len());
+
+            while !rest.is_char_boundary(end) {
+                end -= 1;
+            }
+
+            if end == 0 {
+                end = first.len_utf8();
+            }
+
+            let (chunk, tail) = rest.split_at(end);
+            rest = tail;
+
+            Some(chunk)
+        })
+    }
+
+    /// Checks whether the string is equal to `other` ignoring Unicode case.
+    ///
+    /// Both strings are compared char by char after mapping each char with Unicode simple case folding,
+    /// the `C` and `S` mappings of `CaseFolding.txt`, which fold every char to exactly one char.
+    /// This equates chars that ascii case folding does not, such as `'Ä'` and `'ä'`, `'ẞ'` and `'ß'`,
+    /// or the final and non final forms of sigma. Chars whose only folding is to multiple chars are not expanded,
+    /// so `'ß'` is not equal to `"ss"`, and no locale specific rules are applied, so `'İ'` is only equal to itself.
+    ///
+    /// For ascii only comparison, use [`str::eq_ignore_ascii_case`] through [`Deref`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("Straße ΣΊΣΥΦΟΣ"));
+    ///
+    /// assert!(s.eq_ignore_case("STRAẞE σίσυφος"));
+    /// assert!(!s.eq_ignore_ascii_case("STRAẞE σίσυφος"));
+    /// assert!(!s.eq_ignore_case("STRASSE σίσυφος"));
+    ///
+    /// let dotted = OwnedIndexedChars::new(String::from("İ"));
+    /// assert!(!dotted.eq_ignore_case("i"));
+    /// assert!(!dotted.eq_ignore_case("i\u{307}"));
+    /// # assert!(dotted.eq_ignore_case("İ"));
+    /// ```
+    #[cfg(feature = "unicode-case")]
+    #[must_use]
+    pub fn eq_ignore_case(&self, other: &str) -> bool {
+        let fold = |s| str::chars(s).map(crate::case::simple_fold);
+
+        fold(&self.buf).eq(fold(other))
+    }
+
+    /// Samples every `k`th char of the string, yielding the chars at char indices `0, k, 2k, ...`.
+    ///
+    /// Each sampled char is looked up through the index, so chars in between are never decoded.
+    /// The returned iterator knows its exact length, which is the char count divided by `k` rounded up.
+    ///
+    /// # Panics
+    /// Panics if `k` is 0.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añ💯bçd"));
+    /// let sampled = s.chars_step_by(2);
+    ///
+    /// assert_eq!(sampled.len(), 3);
+    /// assert_eq!(sampled.collect::<String>(), "a💯ç");
+    /// ```
+    #[must_use]
+    pub fn chars_step_by(&self, k: usize) -> impl ExactSizeIterator<Item = char> + '_ {
+        assert!(k != 0, "step must not be 0");
+
+        (0..self.char_count()).step_by(k).map(move |i| {
+            self.inner
+                .get_char(&self.buf, i)
+                .expect("index is in range")
+        })
+    }
+
+    /// Iterates the char ranges of every window of `k` consecutive chars, yielding `i..i + k` for each window start `i`.
+    ///
+    /// The returned iterator knows its exact length, which is `char_count() - (k - 1)`, or 0 if there are fewer than `k` chars.
+    ///
+    /// # Panics
+    /// Panics if `k` is 0.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añ💯b"));
+    /// let windows: Vec<_> = s.char_window_ranges(3).collect();
+    ///
+    /// assert_eq!(windows, [0..3, 1..4]);
+    /// assert_eq!(s.get_char_range(windows[1].clone()), Some("ñ💯b"));
+    /// ```
+    #[must_use]
+    pub fn char_window_ranges(&self, k: usize) -> impl ExactSizeIterator<Item = Range<usize>> {
+        assert!(k != 0, "window size must not be 0");
+
+        (0..self.char_count().saturating_sub(k - 1)).map(move |i| i..i + k)
+    }
+
+    /// Iterates the byte offset of every char boundary, that is the start of every char followed by the byte length of the string.
+    ///
+    /// This yields `char_count() + 1` offsets, and is computed by walking the index without decoding the string.
+    /// The returned iterator knows its exact length.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añ💯"));
+    /// let boundaries: Vec<_> = s.char_boundaries().collect();
+    ///
+    /// assert_eq!(boundaries, [0, 1, 3, 7]);
+    /// assert_eq!(boundaries.len(), s.char_count() + 1);
+    /// ```
+    #[must_use]
+    pub fn char_boundaries(&self) -> impl ExactSizeIterator<Item = usize> + '_ {
+        CharBoundaries {
+            offsets: self.inner.offsets_from(&self.buf, 0),
+            byte_len: Some(self.buf.len()),
+        }
+    }
+
+    /// Returns the approximate number of columns the string takes up when printed to a terminal.
+    ///
+    /// Each char is given a width following a compact approximation of Unicode East Asian Width:
+    /// chars in the major wide and fullwidth blocks (such as CJK ideographs, Hangul syllables and fullwidth forms) and most emoji take up 2 columns,
+    /// control chars, combining marks and zero width chars take up none, and all other chars take up 1.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("ab中文ñ"));
+    ///
+    /// assert_eq!(s.display_width(), 7);
+    /// ```
+    #[cfg(feature = "unicode-width")]
+    #[must_use]
+    pub fn display_width(&self) -> usize {
+        self.buf.chars().map(crate::width::char_width).sum()
+    }
+
+    /// Returns the approximate display width of a range of chars as in [`display_width`][OwnedIndexedChars::display_width],
+    /// or `None` if the range is out of bounds or its start is past its end.
+    ///
+    /// The range is resolved through the index, so only the chars inside it are walked.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("ab中文ñ"));
+    ///
+    /// assert_eq!(s.display_width_of_range(1..3), Some(3));
+    /// assert_eq!(s.display_width_of_range(3..6), None);
+    /// ```
+    #[cfg(feature = "unicode-width")]
+    #[must_use]
+    pub fn display_width_of_range(&self, range: impl RangeBounds<usize>) -> Option<usize> {
+        let range = self.get_char_range(range)?;
+
+        Some(range.chars().map(crate::width::char_width).sum())
+    }
+
+    /// Counts the chars for which `pred` returns true, in a single scan of the string.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("ñ1a23💯4"));
+    ///
+    /// assert_eq!(s.count_chars(|c| c.is_ascii_digit()), 4);
+    /// ```
+    pub fn count_chars(&self, mut pred: impl FnMut(char) -> bool) -> usize {
+        self.buf.chars().filter(|&c| pred(c)).count()
+    }
+
+    /// Counts the occurrences of each char in the backing string in a single scan.
+    ///
+    /// Returns an empty map for an empty string.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añaña"));
+    /// let freq = s.char_frequencies();
+    ///
+    /// assert_eq!(freq[&'a'], 3);
+    /// assert_eq!(freq[&'ñ'], 2);
+    /// assert_eq!(freq.len(), 2);
+    /// ```
+    #[must_use]
+    pub fn char_frequencies(&self) -> BTreeMap<char, usize> {
+        let mut freq = BTreeMap::new();
+
+        for c in self.buf.chars() {
+            *freq.entry(c).or_insert(0) += 1;
+        }
+
+        freq
+    }
+
+    /// Counts the occurrences of each char in the backing string in a single scan, into a [`HashMap`](std::collections::HashMap).
+    ///
+    /// See [`char_frequencies`][OwnedIndexedChars::char_frequencies] for the `no_std` variant.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añaña"));
+    ///
+    /// assert_eq!(s.char_frequencies_hash_map()[&'ñ'], 2);
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn char_frequencies_hash_map(&self) -> std::collections::HashMap<char, usize> {
+        let mut freq = std::collections::HashMap::new();
+
+        for c in self.buf.chars() {
+            *freq.entry(c).or_insert(0) += 1;
+        }
+
+        freq
+    }
+
+    /// Appends a char to the end of the string, extending the index.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut s = OwnedIndexedChars::new(String::from("ab"));
+    /// s.push('ñ');
+    ///
+    /// assert_eq!(s.get_char(2), Some('ñ'));
+    /// ```
+    pub fn push(&mut self, c: char) {
+        self.generation += 1;
+
+        self.inner.push(self.char_count(), self.buf.len(), c);
+        self.buf.push(c);
+    }
+
+    /// Appends a string slice to the end of the string, extending the index.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut s = OwnedIndexedChars::new(String::from("ñ"));
+    /// s.push_str("ab");
+    ///
+    /// assert_eq!(s.get_char(2), Some('b'));
+    /// ```
+    pub fn push_str(&mut self, s: &str) {
+        self.generation += 1;
+
+        self.inner.push_str(&self.buf, s);
+        self.buf.push_str(s);
+    }
+
+    /// Appends another indexed string to the end of this one.
+    ///
+    /// The index of `other` is copied onto the end of this index, so the appended chars do not need to be scanned again.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut s = OwnedIndexedChars::new(String::from("añ"));
+    /// s.append(&OwnedIndexedChars::new(String::from("💯b")));
+    ///
+    /// assert_eq!(s, *"añ💯b");
+    /// assert_eq!(s.get_char(3), Some('b'));
+    /// ```
+    pub fn append(&mut self, other: &OwnedIndexedChars) {
+        self.append_all(&[other]);
+    }
+
+    /// Appends a batch of indexed strings to the end of this one, in order.
+    ///
+    /// Space for the string data and index entries of the whole batch is reserved once up front,
+    /// and the index of each fragment is copied onto the end of this index, so the appended chars do not need to be scanned again.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let spans = ["ñ", "ab", "💯"].map(|s| OwnedIndexedChars::new(String::from(s)));
+    ///
+    /// let mut line = OwnedIndexedChars::new(String::new());
+    /// line.append_all(&[&spans[0], &spans[1], &spans[2]]);
+    ///
+    /// assert_eq!(line, *"ñab💯");
+    /// assert_eq!(line.get_char(3), Some('💯'));
+    /// ```
+    pub fn append_all(&mut self, others: &[&OwnedIndexedChars]) {
+        self.generation += 1;
+
+        self.buf.reserve(others.iter().map(|o| o.buf.len()).sum());
+
+        if !(self.inner.is_ascii() && others.iter().all(|o| o.inner.is_ascii())) {
+            self.inner
+                .reserve(&self.buf, others.iter().map(|o| o.char_count()).sum());
+        }
+
+        for other in others {
+            self.inner.append(&self.buf, &other.buf, &other.inner);
+            self.buf.push_str(&other.buf);
+        }
+    }
+
+    /// Appends a slice of chars to the end of the string, encoding each char and indexing it in the same pass.
+    ///
+    /// Space for the encoded chars and their index entries is reserved up front.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut s = OwnedIndexedChars::new(String::from("a"));
+    /// s.extend_from_chars(&['ñ', 'b']);
+    ///
+    /// assert_eq!(s, *"añb");
+    /// assert_eq!(s.get_char(2), Some('b'));
+    /// ```
+    pub fn extend_from_chars(&mut self, chars: &[char]) {
+        let bytes: usize = chars.iter().map(|c| c.len_utf8()).sum();
+
+        self.buf.reserve(bytes);
+
+        if bytes != chars.len() {
+            self.inner.reserve(&self.buf, chars.len());
+        }
+
+        for &c in chars {
+            self.push(c);
+        }
+    }
+
+    /// Removes the last char from the string and returns it, or `None` if the string is empty.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut s = OwnedIndexedChars::new(String::from("añ"));
+    ///
+    /// assert_eq!(s.pop(), Some('ñ'));
+    /// assert_eq!(s.pop(), Some('a'));
+    /// assert_eq!(s.pop(), None);
+    /// ```
+    pub fn pop(&mut self) -> Option<char> {
+        self.generation += 1;
+
+        let count = self.char_count();
+        let c = self.buf.pop()?;
+
+        self.inner.truncate(&self.buf, count - 1);
+
+        Some(c)
+    }
+
+    /// Shortens the string to its first `n` chars, trimming the index at the same point.
+    ///
+    /// If `n` is greater than or equal to the char count, this has no effect.
+    /// This mirrors [`String::truncate`] in char coordinates.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut s = OwnedIndexedChars::new(String::from("añ💯b"));
+    /// s.truncate_chars(2);
+    ///
+    /// assert_eq!(s, *"añ");
+    /// assert_eq!(s.get_char(2), None);
+    /// ```
+    pub fn truncate_chars(&mut self, n: usize) {
+        self.generation += 1;
+
+        if let Some(byte) = self.inner.byte_offset(&self.buf, n) {
+            self.buf.truncate(byte);
+            self.inner.truncate(&self.buf, n);
+        }
+    }
+
+    /// Inserts a char at the char index `index`, shifting all chars after it.
+    ///
+    /// Unlike [`String::insert`], `index` is a char index and not a byte index.
+    /// The index is rebuilt from the insertion point onwards.
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than [`char_count`][OwnedIndexedChars::char_count].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut s = OwnedIndexedChars::new(String::from("ñb"));
+    /// s.insert(1, 'a');
+    ///
+    /// assert_eq!(s, *"ñab");
+    /// assert_eq!(s.get_char(2), Some('b'));
+    /// ```
+    pub fn insert(&mut self, index: usize, c: char) {
+        self.generation += 1;
+
+        let byte = self
+            .inner
+            .char_boundary(&self.buf, index)
+            .expect("char index out of bounds");
+
+        self.buf.insert(byte, c);
+        self.inner.rebuild_from(&self.buf, byte);
+    }
+
+    /// Inserts another indexed string at the char index `index`, shifting all chars after it.
+    ///
+    /// The index of `other` is spliced into this index, so the inserted chars do not need to be scanned again.
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than [`char_count`][OwnedIndexedChars::char_count].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut s = OwnedIndexedChars::new(String::from("ñb"));
+    /// s.insert_indexed(1, &OwnedIndexedChars::new(String::from("💯a")));
+    ///
+    /// assert_eq!(s, *"ñ💯ab");
+    /// assert_eq!(s.get_char(3), Some('b'));
+    /// ```
+    pub fn insert_indexed(&mut self, index: usize, other: &OwnedIndexedChars) {
+        self.generation += 1;
+
+        let byte = self
+            .inner
+            .char_boundary(&self.buf, index)
+            .expect("char index out of bounds");
+
+        self.inner = self.inner.splice(
+            &self.buf,
+            index..index,
+            byte..byte,
+            &other.buf,
+            &other.inner,
+        );
+        self.buf.insert_str(byte, &other.buf);
+    }
+
+    /// Replaces a range of chars with another indexed string, shifting all chars after it.
+    ///
+    /// The index of `replacement` and of the chars around the range are spliced together,
+    /// so neither the replacement nor the rest of the string need to be scanned again.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds, or its start is past its end.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut s = OwnedIndexedChars::new(String::from("añ💯b"));
+    /// s.replace_char_range_with(1..3, &OwnedIndexedChars::new(String::from("çdé")));
+    ///
+    /// assert_eq!(s, *"açdéb");
+    /// assert_eq!(s.get_char(4), Some('b'));
+    /// ```
+    pub fn replace_char_range_with(
+        &mut self,
+        range: impl RangeBounds<usize>,
+        replacement: &OwnedIndexedChars,
+    ) {
+        self.generation += 1;
+
+        let chars = self
+            .inner
+            .char_range(&self.buf, range)
+            .expect("char range out of bounds");
+        let bytes = self
+            .inner
+            .byte_range(&self.buf, chars.clone())
+            .expect("char range out of bounds");
+
+        self.inner = self.inner.splice(
+            &self.buf,
+            chars,
+            bytes.clone(),
+            &replacement.buf,
+            &replacement.inner,
+        );
+        self.buf.replace_range(bytes, &replacement.buf);
+    }
+
+    /// Replaces a range of bytes with `replacement`, for callers whose edits are in byte offsets.
+    ///
+    /// The index is rebuilt from the start of the range onwards.
+    /// See [`replace_char_range_with`][OwnedIndexedChars::replace_char_range_with] for replacing a range in char coordinates.
+    ///
+    /// # Panics
+    /// Panics if either end of the range does not lie on a char boundary, or if it is out of bounds, like [`String::replace_range`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut s = OwnedIndexedChars::new(String::from("añ💯b"));
+    /// s.replace_byte_range(1..7, "ç");
+    ///
+    /// assert_eq!(s, *"açb");
+    /// assert_eq!(s.get_char(2), Some('b'));
+    /// ```
+    pub fn replace_byte_range(&mut self, range: Range<usize>, replacement: &str) {
+        self.generation += 1;
+
+        let start = range.start;
+
+        self.buf.replace_range(range, replacement);
+        self.inner.rebuild_from(&self.buf, start);
+    }
+
+    /// Removes the char at the char index `index` and returns it, shifting all chars after it.
+    ///
+    /// Unlike [`String::remove`], `index` is a char index and not a byte index.
+    /// The index is rebuilt from the removal point onwards.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of range.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut s = OwnedIndexedChars::new(String::from("ñab"));
+    ///
+    /// assert_eq!(s.remove(1), 'a');
+    /// assert_eq!(s.get_char(1), Some('b'));
+    /// ```
+    pub fn remove(&mut self, index: usize) -> char {
+        self.generation += 1;
+
+        let byte = self
+            .inner
+            .byte_offset(&self.buf, index)
+            .expect("char index out of bounds");
+
+        let c = self.buf.remove(byte);
+        self.inner.rebuild_from(&self.buf, byte);
+
+        c
+    }
+
+    /// Replaces every occurrence of the char `from` with `to`, returning the number of replacements made.
+    ///
+    /// When both chars have the same utf8 length, the bytes are rewritten in place and the index is left untouched,
+    /// as no char boundaries move. Otherwise the string and its index are rebuilt in a single pass.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut s = OwnedIndexedChars::new(String::from("banana"));
+    ///
+    /// assert_eq!(s.replace_all_char('a', 'o'), 3);
+    /// assert_eq!(s, *"bonono");
+    ///
+    /// assert_eq!(s.replace_all_char('o', 'ä'), 3);
+    /// assert_eq!(s, *"bänänä");
+    /// assert_eq!(s.get_char(5), Some('ä'));
+    /// ```
+    pub fn replace_all_char(&mut self, from: char, to: char) -> usize {
+        self.generation += 1;
+
+        let mut count = 0;
+
+        if from.len_utf8() == to.len_utf8() {
+            let mut encoded = [0; 4];
+            let to = &*to.encode_utf8(&mut encoded);
+            let mut start = 0;
+
+            while let Some(i) = self.buf[start..].find(from) {
+                let at = start + i;
+                start = at + to.len();
+
+                self.buf.replace_range(at..start, to);
+                count += 1;
+            }
+        } else {
+            let chars = self.buf.chars().map(|c| {
+                if c == from {
+                    count += 1;
+                    to
+                } else {
+                    c
+                }
+            });
+
+            let replaced = Self::from_chars_iter(chars, self.buf.len());
+
+            self.buf = replaced.buf;
+            self.inner = replaced.inner;
+        }
+
+        count
+    }
+
+    /// Truncates the string so that its [`display_width`][OwnedIndexedChars::display_width] is at most `max_cols` columns, trimming the index at the same point.
+    ///
+    /// Chars are kept from the start of the string until the next char would exceed the budget,
+    /// so a wide char that would straddle the limit is dropped entirely.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut s = OwnedIndexedChars::new(String::from("ab中文ñ"));
+    /// s.truncate_to_width(5);
+    ///
+    /// assert_eq!(s, *"ab中");
+    /// assert_eq!(s.display_width(), 4);
+    /// ```
+    #[cfg(feature = "unicode-width")]
+    pub fn truncate_to_width(&mut self, max_cols: usize) {
+        self.generation += 1;
+
+        let mut width = 0;
+
+        let cut = self.buf.char_indices().enumerate().find(|&(_, (_, c))| {
+            width += crate::width::char_width(c);
+            width > max_cols
+        });
+
+        if let Some((index, (byte, _))) = cut {
+            self.buf.truncate(byte);
+            self.inner.truncate(&self.buf, index);
+        }
+    }
+
+    /// Rotates the chars of the string left by `n` positions (modulo the char count), so the char at `n` becomes the first char.
+    ///
+    /// The index of the rotated string is built from the two rotated parts of the existing index, so no chars are scanned.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut s = OwnedIndexedChars::new(String::from("añ💯b"));
+    /// s.rotate_left_chars(1);
+    ///
+    /// assert_eq!(s, *"ñ💯ba");
+    /// assert_eq!(s.get_char(1), Some('💯'));
+    /// ```
+    pub fn rotate_left_chars(&mut self, n: usize) {
+        self.generation += 1;
+
+        let count = self.char_count();
+
+        if count == 0 || n % count == 0 {
+            return;
+        }
+
+        let n = n % count;
+        let byte = self
+            .inner
+            .char_boundary(&self.buf, n)
+            .unwrap_or_else(|| unreachable!("rotation is less than the char count"));
+
+        let (head, tail) = self.buf.split_at(byte);
+
+        let mut inner = self.inner.slice(&self.buf, n..count, byte..self.buf.len());
+        let head_inner = self.inner.slice(&self.buf, 0..n, 0..byte);
+
+        let mut buf = String::with_capacity(self.buf.len());
+        buf.push_str(tail);
+
+        inner.append(&buf, head, &head_inner);
+        buf.push_str(head);
+
+        self.buf = buf;
+        self.inner = inner;
+    }
+
+    /// Rotates the chars of the string right by `n` positions (modulo the char count), so the last `n` chars move to the front.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut s = OwnedIndexedChars::new(String::from("añ💯b"));
+    /// s.rotate_right_chars(1);
+    ///
+    /// assert_eq!(s, *"bañ💯");
+    /// assert_eq!(s.get_char(3), Some('💯'));
+    /// ```
+    pub fn rotate_right_chars(&mut self, n: usize) {
+        let count = self.char_count();
+
+        if count == 0 {
+            self.generation += 1;
+            return;
+        }
+
+        self.rotate_left_chars(count - n % count);
+    }
+
+    /// Splits the string in two at the char index `index`, keeping the chars before it in `self` and returning the rest.
+    ///
+    /// This mirrors [`String::split_off`] in char coordinates. The index of the returned half is built
+    /// from the existing index rather than scanning the string again.
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than [`char_count`][OwnedIndexedChars::char_count].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut s = OwnedIndexedChars::new(String::from("ab💯ñ"));
+    /// let right = s.split_off_chars(2);
+    ///
+    /// assert_eq!(s, *"ab");
+    /// assert_eq!(right, *"💯ñ");
+    /// assert_eq!(right.get_char(1), Some('ñ'));
+    /// ```
+    #[must_use]
+    pub fn split_off_chars(&mut self, index: usize) -> OwnedIndexedChars {
+        self.generation += 1;
+
+        let byte = self
+            .inner
+            .char_boundary(&self.buf, index)
+            .expect("char index out of bounds");
+
+        let buf = self.buf.split_off(byte);
+        let inner = self.inner.split_off(&self.buf, &buf, index);
+
+        Self::from_inner(buf, inner)
+    }
+
+    /// Splits the string after its first `n` chars, borrowing both halves.
+    ///
+    /// If `n` is greater than or equal to the char count, the whole string is returned as the prefix and the suffix is empty.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añ💯b"));
+    ///
+    /// assert_eq!(s.split_prefix_chars(2), ("añ", "💯b"));
+    /// assert_eq!(s.split_prefix_chars(0), ("", "añ💯b"));
+    /// assert_eq!(s.split_prefix_chars(10), ("añ💯b", ""));
+    /// ```
+    #[must_use]
+    pub fn split_prefix_chars(&self, n: usize) -> (&str, &str) {
+        let byte = self
+            .inner
+            .char_boundary(&self.buf, n)
+            .unwrap_or(self.buf.len());
+
+        self.buf.split_at(byte)
+    }
+
+    /// Splits the string at the char index `index`, borrowing both halves, or returns `None` if `index` is greater than the char count.
+    ///
+    /// Unlike [`split_prefix_chars`][OwnedIndexedChars::split_prefix_chars], an out of range index is not clamped.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añ💯"));
+    ///
+    /// assert_eq!(s.split_at_char(1), Some(("a", "ñ💯")));
+    /// assert_eq!(s.split_at_char(3), Some(("añ💯", "")));
+    /// assert_eq!(s.split_at_char(4), None);
+    /// ```
+    #[must_use]
+    pub fn split_at_char(&self, index: usize) -> Option<(&str, &str)> {
+        let byte = self.inner.char_boundary(&self.buf, index)?;
+
+        Some(self.buf.split_at(byte))
+    }
+
+    /// Splits the string at the char index `index`, borrowing both halves.
+    ///
+    /// This behaves like [`split_at_char`][OwnedIndexedChars::split_at_char], but reports the char count on failure
+    /// so callers may clamp or report the valid bounds. Prefer this in library code that propagates errors with `?`,
+    /// and the `Option` variant when an out of range index is simply handled in place.
+    ///
+    /// # Errors
+    /// Returns the char count of the string if `index` is greater than it.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añ💯"));
+    ///
+    /// assert_eq!(s.split_at_char_checked(2), Ok(("añ", "💯")));
+    /// assert_eq!(s.split_at_char_checked(5), Err(3));
+    /// ```
+    pub fn split_at_char_checked(&self, index: usize) -> Result<(&str, &str), usize> {
+        self.split_at_char(index).ok_or_else(|| self.char_count())
+    }
+
+    /// Consumes the string and splits it after its first `n` chars, returning both halves as indexed strings.
+    ///
+    /// The index of each half is built by partitioning the existing index rather than rescanning the string.
+    /// If `n` is greater than or equal to the char count, the whole string is returned as the prefix and the suffix is empty.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añ💯b"));
+    /// let (prefix, suffix) = s.take_prefix_chars(2);
+    ///
+    /// assert_eq!(prefix, *"añ");
+    /// assert_eq!(suffix.get_char(0), Some('💯'));
+    /// ```
+    #[must_use]
+    pub fn take_prefix_chars(mut self, n: usize) -> (OwnedIndexedChars, OwnedIndexedChars) {
+        let suffix = self.split_off_chars(n.min(self.char_count()));
+
+        (self, suffix)
+    }
+
+    /// Removes every char outside of the char range `range` in place, keeping only the chars inside it.
+    ///
+    /// The backing string is compacted and the index of the kept chars is rebased in a single pass.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds, or its start is past its end.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut s = OwnedIndexedChars::new(String::from("ñab💯"));
+    /// s.keep_char_range(1..3);
+    ///
+    /// assert_eq!(s, *"ab");
+    /// assert!(s.is_ascii());
+    /// ```
+    pub fn keep_char_range(&mut self, range: impl RangeBounds<usize>) {
+        self.generation += 1;
+
+        let chars = self
+            .inner
+            .char_range(&self.buf, range)
+            .expect("char range out of bounds");
+        let bytes = self
+            .inner
+            .byte_range(&self.buf, chars.clone())
+            .expect("char range out of bounds");
+
+        self.inner = self.inner.slice(&self.buf, chars, bytes.clone());
+
+        self.buf.truncate(bytes.end);
+        self.buf.drain(..bytes.start);
+    }
+
+    /// Removes consecutive repeated chars, compacting the backing string and rebuilding the index in one pass.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut s = OwnedIndexedChars::new(String::from("aaabbb"));
+    /// s.dedup_adjacent_chars();
+    ///
+    /// assert_eq!(s, *"ab");
+    /// assert_eq!(s.char_count(), 2);
+    /// ```
+    pub fn dedup_adjacent_chars(&mut self) {
+        self.dedup_adjacent_chars_by(|a, b| a == b);
+    }
+
+    /// Removes consecutive chars that `same` considers equal, keeping the first of each run.
+    ///
+    /// `same` is called with the current char and the previously kept char, in the same order as [`Vec::dedup_by`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut s = OwnedIndexedChars::new(String::from("a \t\u{3000}b  c"));
+    /// s.dedup_adjacent_chars_by(|a, b| a.is_whitespace() && b.is_whitespace());
+    ///
+    /// assert_eq!(s, *"a b c");
+    /// assert_eq!(s.get_char(4), Some('c'));
+    ///
+    /// // removes uppercase repeats of the kept lowercase char
+    /// let mut s = OwnedIndexedChars::new(String::from("aAbBBñÑ"));
+    /// s.dedup_adjacent_chars_by(|current, previous| current == previous.to_ascii_uppercase());
+    ///
+    /// assert_eq!(s, *"abñÑ");
+    /// ```
+    pub fn dedup_adjacent_chars_by(&mut self, mut same: impl FnMut(char, char) -> bool) {
+        let mut prev = None;
+
+        self.retain(|c| {
+            if matches!(prev, Some(p) if same(c, p)) {
+                return false;
+            }
+
+            prev = Some(c);
+            true
+        });
+    }
+
+    /// Retains only the chars for which `f` returns true, compacting the backing string and rebuilding the index in one pass.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut s = OwnedIndexedChars::new(String::from("a1ñ2b"));
+    /// s.retain(char::is_alphabetic);
+    ///
+    /// assert_eq!(s, *"añb");
+    /// assert_eq!(s.get_char(2), Some('b'));
+    /// ```
+    pub fn retain(&mut self, mut f: impl FnMut(char) -> bool) {
+        self.retain_indexed(|_, c| f(c));
+    }
+
+    /// Retains only the chars for which `f` returns true, where `f` is also passed the char index of each char,
+    /// compacting the backing string and rebuilding the index in one pass.
+    ///
+    /// The index passed to `f` is the position of the char in the original string, not in the compacted one.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut s = OwnedIndexedChars::new(String::from("añbçdé"));
+    /// s.retain_indexed(|i, _| !(1..4).contains(&i));
+    ///
+    /// assert_eq!(s, *"adé");
+    /// assert_eq!(s.get_char(2), Some('é'));
+    /// ```
+    pub fn retain_indexed(&mut self, mut f: impl FnMut(usize, char) -> bool) {
+        self.generation += 1;
+
+        let mut inner = IndexedCharsInner::default();
+        let (mut original_idx, mut char_idx, mut real_idx) = (0, 0, 0);
+
+        self.buf.retain(|c| {
+            let keep = f(original_idx, c);
+            original_idx += 1;
+
+            if keep {
+                inner.push(char_idx, real_idx, c);
+                char_idx += 1;
+                real_idx += c.len_utf8();
+            }
+
+            keep
+        });
+
+        self.inner = inner;
+    }
+
+    /// Shrinks the capacity of the backing string and the index to fit their contents.
+    pub fn shrink_to_fit(&mut self) {
+        self.buf.shrink_to_fit();
+        self.inner.shrink_to_fit();
+    }
+
+    /// Shrinks the capacity of the backing string towards `min_bytes`, like [`String::shrink_to`],
+    /// and the capacity of the index by the same proportion.
+    ///
+    /// Capacity is never reduced below what is currently in use, and is left alone if it is already below `min_bytes`.
+    /// This keeps some headroom for reused buffers, where [`shrink_to_fit`](Self::shrink_to_fit) would discard it all.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut s = OwnedIndexedChars::with_capacity_for(1000, 500);
+    /// s.push_str("añb");
+    ///
+    /// s.shrink_to(100);
+    /// assert!(s.as_str().len() <= s.as_string().capacity());
+    /// assert!(s.as_string().capacity() < 1000);
+    /// # assert!(s.as_string().capacity() >= 100);
+    /// # assert_eq!(s.get_char(1), Some('ñ'));
+    /// ```
+    pub fn shrink_to(&mut self, min_bytes: usize) {
+        self.buf.shrink_to(min_bytes);
+        self.inner.shrink_to(&self.buf, min_bytes);
+    }
+
+    /// Recomputes the index from scratch from the current string.
+    ///
+    /// Edits that rebuild the index from the edit point keep it exact, so this never changes the value of any lookup.
+    /// It is intended to be called occasionally after a burst of edits, where it helps in two ways: the offset and rollover tables
+    /// of the index are reallocated to their exact lengths, dropping capacity left over from the strings it indexed before, and any layout
+    /// left behind by the edits is replaced with the one a freshly indexed string would have, so lookups take no more
+    /// search steps than for a new value. Unlike [`shrink_to_fit`](Self::shrink_to_fit) this also shrinks the index when
+    /// the string itself has spare capacity that should be kept.
+    ///
+    /// This takes time linear in the length of the string, and does not change the [`generation`](Self::generation).
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut s = OwnedIndexedChars::new("ñ".repeat(1000));
+    /// s.truncate_chars(10);
+    /// s.insert(5, '💯');
+    ///
+    /// s.optimize_index();
+    /// assert_eq!(s.get_char(5), Some('💯'));
+    /// # assert_eq!(s.get_char(10), Some('ñ'));
+    /// ```
+    pub fn optimize_index(&mut self) {
+        self.inner = IndexedCharsInner::new(&self.buf);
+        // the rollover table is grown by pushing, so it may still have spare capacity
+        self.inner.shrink_to_fit();
+    }
+
+    /// Converts this value into a [`BoxedIndexedChars`], a read only form with no spare capacity in the string or the index.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut s = OwnedIndexedChars::with_capacity_for(100, 10);
+    /// s.push_str("añb");
+    ///
+    /// let boxed = s.into_boxed();
+    /// assert_eq!(boxed.get_char(1), Some('ñ'));
+    /// ```
+    #[must_use]
+    pub fn into_boxed(self) -> BoxedIndexedChars {
+        BoxedIndexedChars::from_inner(self.buf.into_boxed_str(), self.inner)
+    }
+
+    /// Converts this value into a [`SharedIndexedChars`], whose clones share the string and index.
+    ///
+    /// The index is shrunk to fit, as the result is immutable.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("foñ")).into_shared();
+    /// let clone = s.clone();
+    ///
+    /// assert_eq!(clone.get_char(2), Some('ñ'));
+    /// ```
+    #[must_use]
+    pub fn into_shared(self) -> SharedIndexedChars {
+        SharedIndexedChars::from_inner(&self.buf, self.inner)
+    }
+
+    /// Drops index data and returns backing `String` allocation.
+    ///
+    /// This is the way to release the memory of the index under memory pressure,
+    /// the index can be rebuilt later by passing the string back to [`new`][OwnedIndexedChars::new].
+    #[must_use]
+    pub fn into_string(self) -> String {
+        self.buf
+    }
+
+    /// Splits this value into its backing `String` allocation and its index, so they may be stored separately.
+    ///
+    /// The parts may be rejoined with [`from_raw_parts`][OwnedIndexedChars::from_raw_parts].
     #[must_use]
-    pub fn char_count(&self) -> usize {
-        self.inner.char_count(&self.buf)
+    pub fn into_parts(self) -> (String, IndexedCharsInner) {
+        (self.buf, self.inner)
     }
 
-    /// Drops index data and returns backing `String` allocation.
+    /// Serializes the index alone into a versioned byte snapshot, so it may be stored apart from the string.
+    ///
+    /// The snapshot can be reattached to the string with [`attach_index`][OwnedIndexedChars::attach_index].
+    /// Its format is independent of the platform, and is tagged with a version so that snapshots
+    /// written in a format this version of the crate does not know are rejected instead of misread.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let (buf, snapshot) = {
+    ///     let s = OwnedIndexedChars::new("ñ".repeat(300));
+    ///     let snapshot = s.index_snapshot();
+    ///     (s.into_string(), snapshot)
+    /// };
+    ///
+    /// let s = OwnedIndexedChars::attach_index(buf, &snapshot).unwrap();
+    /// assert_eq!(s.get_char(299), Some('ñ'));
+    /// ```
     #[must_use]
-    pub fn into_string(self) -> String {
-        self.buf
+    pub fn index_snapshot(&self) -> Vec<u8> {
+        self.inner.to_snapshot()
     }
 
     /// Returns a reference to the backing `String` allocation.
@@ -108,6 +2719,26 @@ impl AsRef<str> for OwnedIndexedChars {
     }
 }
 
+impl AsRef<[u8]> for OwnedIndexedChars {
+    fn as_ref(&self) -> &[u8] {
+        self.buf.as_bytes()
+    }
+}
+
+#[cfg(feature = "std")]
+impl AsRef<std::ffi::OsStr> for OwnedIndexedChars {
+    fn as_ref(&self) -> &std::ffi::OsStr {
+        self.buf.as_ref()
+    }
+}
+
+#[cfg(feature = "std")]
+impl AsRef<std::path::Path> for OwnedIndexedChars {
+    fn as_ref(&self) -> &std::path::Path {
+        self.buf.as_ref()
+    }
+}
+
 impl Borrow<str> for OwnedIndexedChars {
     fn borrow(&self) -> &str {
         self
@@ -130,7 +2761,8 @@ impl Eq for OwnedIndexedChars {}
 
 impl PartialEq for OwnedIndexedChars {
     fn eq(&self, other: &Self) -> bool {
-        self.buf.eq(&other.buf)
+        // differing char counts are an O(1) reject even when the byte lengths are equal
+        self.same_char_count(other) && self.buf.eq(&other.buf)
     }
 }
 
@@ -146,6 +2778,29 @@ impl PartialEq<OwnedIndexedChars> for str {
     }
 }
 
+/// Compares the string to a single char, this is only true if the string consists of
+/// exactly that one char, and is always false for empty or multi char strings.
+///
+/// # Examples
+/// ```rust
+/// # use char_index::OwnedIndexedChars;
+/// assert_eq!(OwnedIndexedChars::new(String::from("ñ")), 'ñ');
+/// assert_ne!(OwnedIndexedChars::new(String::from("ññ")), 'ñ');
+/// assert_ne!(OwnedIndexedChars::new(String::new()), 'ñ');
+/// # assert_eq!('ñ', OwnedIndexedChars::new(String::from("ñ")));
+/// ```
+impl PartialEq<char> for OwnedIndexedChars {
+    fn eq(&self, other: &char) -> bool {
+        self.buf.as_str() == other.encode_utf8(&mut [0; 4])
+    }
+}
+
+impl PartialEq<OwnedIndexedChars> for char {
+    fn eq(&self, other: &OwnedIndexedChars) -> bool {
+        other == self
+    }
+}
+
 impl Ord for OwnedIndexedChars {
     fn cmp(&self, other: &Self) -> Ordering {
         self.buf.cmp(&other.buf)
@@ -175,3 +2830,867 @@ impl Hash for OwnedIndexedChars {
         self.buf.hash(state);
     }
 }
+
+#[test]
+fn dedup_adjacent_chars_by() {
+    use alloc::vec::Vec;
+
+    let mut calls = Vec::new();
+    let mut s = OwnedIndexedChars::new(String::from("abñ"));
+    s.dedup_adjacent_chars_by(|current, previous| {
+        calls.push((current, previous));
+        false
+    });
+    assert_eq!(calls, [('b', 'a'), ('ñ', 'b')]);
+
+    // an asymmetric predicate: drop a char that is greater than the kept char before it
+    let mut s = OwnedIndexedChars::new(String::from("acbñaé"));
+    s.dedup_adjacent_chars_by(|current, previous| current > previous);
+    assert_eq!(s.as_str(), "aa");
+
+    let mut v: Vec<char> = "acbñaé".chars().collect();
+    v.dedup_by(|current, previous| current > previous);
+    assert_eq!(v, ['a', 'a']);
+}
+
+#[test]
+fn char_index_to_line_col() {
+    let s = OwnedIndexedChars::new("añ\n\nb💯ç\n".repeat(50));
+    let lines = s.line_start_offsets();
+
+    let (mut line, mut col) = (0, 0);
+
+    for (i, c) in s.buf.chars().enumerate() {
+        assert_eq!(s.char_index_to_line_col(&lines, i), (line, col));
+
+        if c == '\n' {
+            line += 1;
+            col = 0;
+        } else {
+            col += 1;
+        }
+    }
+
+    assert_eq!(s.char_index_to_line_col(&lines, s.char_count()), (150, 0));
+}
+
+#[test]
+#[should_panic = "line start table does not start at char index 0"]
+fn char_index_to_line_col_rejects_foreign_tables() {
+    let s = OwnedIndexedChars::new(String::from("añ\nb"));
+    let _ = s.char_index_to_line_col(&[1, 3], 2);
+}
+
+#[test]
+fn char_count_after_mutations() {
+    let mut s = OwnedIndexedChars::new("aé💯".repeat(200));
+
+    let check = |s: &OwnedIndexedChars| {
+        assert_eq!(s.char_count(), s.chars().count());
+        assert!(s.inner.matches(&s.buf));
+    };
+
+    s.push('ñ');
+    check(&s);
+    s.insert(0, '💯');
+    check(&s);
+    s.insert(300, 'x');
+    check(&s);
+    s.push_str(&"é".repeat(300));
+    check(&s);
+    assert_eq!(s.remove(1), 'a');
+    check(&s);
+    assert_eq!(s.remove(0), '💯');
+    check(&s);
+
+    while s.pop().is_some() {
+        check(&s);
+    }
+
+    s.push('a');
+    check(&s);
+    s.insert(0, 'é');
+    check(&s);
+    s.remove(0);
+    check(&s);
+}
+
+#[test]
+fn split_off_chars() {
+    let base = "aé💯b".repeat(200) + &"x".repeat(300) + &"é".repeat(300);
+
+    for index in [0, 1, 2, 3, 255, 256, 500, 800, 1100, 1400] {
+        let mut left = OwnedIndexedChars::new(base.clone());
+        let right = left.split_off_chars(index);
+
+        assert_eq!(left.inner, IndexedCharsInner::new(&left.buf));
+        assert_eq!(right.inner, IndexedCharsInner::new(&right.buf));
+        assert_eq!(left.buf + &right.buf, base);
+    }
+}
+
+#[test]
+fn take_prefix_chars() {
+    let base = "aé💯b".repeat(200);
+    let count = base.chars().count();
+
+    for n in [0, 1, 300, count, count + 1, usize::MAX] {
+        let s = OwnedIndexedChars::new(base.clone());
+        let borrowed = s.split_prefix_chars(n);
+        let (borrowed_prefix, borrowed_suffix) =
+            (String::from(borrowed.0), String::from(borrowed.1));
+
+        let (prefix, suffix) = s.take_prefix_chars(n);
+
+        assert_eq!(prefix.char_count(), n.min(count));
+        assert_eq!(prefix.inner, IndexedCharsInner::new(&prefix.buf));
+        assert_eq!(suffix.inner, IndexedCharsInner::new(&suffix.buf));
+        assert_eq!(prefix.buf, borrowed_prefix);
+        assert_eq!(suffix.buf, borrowed_suffix);
+    }
+}
+
+#[test]
+fn chunks_by_bytes() {
+    let base = "aé💯b".repeat(100);
+
+    for max_bytes in 0..12 {
+        let chunks: Vec<_> = OwnedIndexedChars::new(base.clone())
+            .chunks_by_bytes(max_bytes)
+            .map(String::from)
+            .collect();
+
+        assert_eq!(chunks.concat(), base);
+
+        for chunk in &chunks {
+            assert!(chunk.len() <= max_bytes || chunk.chars().count() == 1);
+        }
+    }
+}
+
+#[test]
+fn matches_at() {
+    let s = OwnedIndexedChars::new("é".repeat(256) + "let💯");
+
+    assert_eq!(s.block_of_char(256).unwrap().chars.start, 256);
+    assert!(s.matches_at(255, "élet"));
+    assert!(s.matches_at(256, "let💯"));
+    assert!(!s.matches_at(256, "let💯!"));
+    assert!(s.matches_at(259, "💯"));
+    assert!(s.matches_at(260, ""));
+    assert!(!s.matches_at(261, ""));
+}
+
+#[test]
+fn keep_char_range() {
+    let base = "aé💯b".repeat(200);
+
+    for range in [0..0, 0..800, 1..799, 300..500, 256..257, 799..800] {
+        let mut s = OwnedIndexedChars::new(base.clone());
+        s.keep_char_range(range.clone());
+
+        let expected: String = base.chars().skip(range.start).take(range.len()).collect();
+
+        assert_eq!(s.buf, expected);
+        assert_eq!(s.inner, IndexedCharsInner::new(&s.buf));
+    }
+}
+
+#[test]
+fn from_utf8_lossy() {
+    let mut bytes = "aé💯b".repeat(100).into_bytes();
+    bytes.extend_from_slice(b"\xff\xe2\x82");
+    bytes.extend("é".repeat(300).bytes());
+    bytes.push(0xc3);
+
+    let s = OwnedIndexedChars::from_utf8_lossy(&bytes);
+
+    assert_eq!(s.buf, String::from_utf8_lossy(&bytes));
+    assert_eq!(s.inner, IndexedCharsInner::new(&s.buf));
+    assert_eq!(s.get_char(400), Some(char::REPLACEMENT_CHARACTER));
+    assert_eq!(s.get_char(401), Some(char::REPLACEMENT_CHARACTER));
+    assert_eq!(s.get_char(402), Some('é'));
+
+    assert!(OwnedIndexedChars::from_utf8_lossy(b"abc").inner.is_ascii());
+}
+
+#[test]
+fn char_boundaries() {
+    let base = "aé💯b".repeat(200);
+    let s = OwnedIndexedChars::new(base.clone());
+
+    let expected: Vec<_> = base
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain([base.len()])
+        .collect();
+
+    assert_eq!(s.char_boundaries().collect::<Vec<_>>(), expected);
+    assert_eq!(s.char_boundaries().last(), Some(s.len()));
+}
+
+#[test]
+fn slice_to_owned() {
+    let base = "aé💯b".repeat(200);
+    let s = OwnedIndexedChars::new(base.clone());
+
+    for range in [0..0, 0..800, 1..799, 300..500, 256..257, 799..800] {
+        let slice = s.slice_to_owned(range.clone()).unwrap();
+
+        assert_eq!(slice.inner, IndexedCharsInner::new(&slice.buf));
+        assert_eq!(slice.char_count(), range.len());
+        assert_eq!(
+            slice.get_char(0),
+            s.get_char(range.start).filter(|_| !range.is_empty())
+        );
+    }
+
+    assert!(s.slice_to_owned(700..801).is_none());
+}
+
+#[test]
+fn generation() {
+    let mut s = OwnedIndexedChars::new("aé💯b".repeat(100));
+
+    let mut last = s.generation();
+    let mut check = |s: &OwnedIndexedChars, bumped: bool| {
+        assert_eq!(s.generation() > last, bumped);
+        last = s.generation();
+    };
+
+    _ = s.get_char(10);
+    _ = s.char_count();
+    _ = s.slice_to_owned(1..3);
+    s.shrink_to_fit();
+    s.optimize_index();
+    check(&s, false);
+
+    s.push('ñ');
+    check(&s, true);
+    s.push_str("ab");
+    check(&s, true);
+    s.extend_from_chars(&['💯']);
+    check(&s, true);
+    s.append(&OwnedIndexedChars::new(String::from("ç")));
+    check(&s, true);
+    s.pop();
+    check(&s, true);
+    s.truncate_chars(400);
+    check(&s, true);
+    s.insert(1, 'x');
+    check(&s, true);
+    s.insert_indexed(1, &OwnedIndexedChars::new(String::from("yz")));
+    check(&s, true);
+    s.remove(0);
+    check(&s, true);
+    s.replace_char_range_with(2..4, &OwnedIndexedChars::new(String::from("ñ")));
+    check(&s, true);
+    s.replace_all_char('é', 'e');
+    check(&s, true);
+    _ = s.split_off_chars(300);
+    check(&s, true);
+    s.keep_char_range(1..);
+    check(&s, true);
+    s.dedup_adjacent_chars();
+    check(&s, true);
+    s.retain(|c| c != 'a');
+    check(&s, true);
+    s.retain_indexed(|i, _| i > 0);
+    check(&s, true);
+}
+
+#[test]
+fn append_all() {
+    let fragments = ["", "abc", "é", &"💯".repeat(100), &"aé💯b".repeat(200)]
+        .map(|s| OwnedIndexedChars::new(String::from(s)));
+
+    for base in ["", "xyz", &"é".repeat(300)] {
+        let mut batched = OwnedIndexedChars::new(String::from(base));
+        let mut sequential = OwnedIndexedChars::new(String::from(base));
+
+        batched.append_all(&fragments.iter().collect::<Vec<_>>());
+
+        for fragment in &fragments {
+            sequential.append(fragment);
+        }
+
+        assert_eq!(batched.buf, sequential.buf);
+        assert_eq!(batched.inner, sequential.inner);
+        assert_eq!(batched.inner, IndexedCharsInner::new(&batched.buf));
+    }
+
+    let mut ascii = OwnedIndexedChars::new(String::from("abc"));
+    ascii.append(&fragments[1]);
+    assert!(ascii.inner.is_ascii());
+}
+
+#[test]
+fn byte_ranges_of_char_ranges() {
+    let s = OwnedIndexedChars::new("aé💯b".repeat(200) + &"é".repeat(600));
+
+    let sorted: Vec<_> = (0..1400).step_by(7).map(|i| i..i + 3).collect();
+    let mut unsorted = sorted.clone();
+    unsorted.reverse();
+
+    let sorted_result = s.byte_ranges_of_char_ranges(&sorted);
+    let mut unsorted_result = s.byte_ranges_of_char_ranges(&unsorted);
+    unsorted_result.reverse();
+
+    assert_eq!(sorted_result, unsorted_result);
+
+    for (range, result) in sorted.into_iter().zip(sorted_result) {
+        assert_eq!(result, s.inner.byte_range(&s.buf, range));
+    }
+
+    assert_eq!(
+        s.byte_ranges_of_char_ranges(&[0..1, 1399..1400, 1400..1401]),
+        [Some(0..1), Some(s.len() - 2..s.len()), None]
+    );
+}
+
+#[test]
+fn index_stats() {
+    let mut s = OwnedIndexedChars::new("中文".repeat(1000));
+    s.shrink_to_fit();
+
+    let stats = s.index_stats();
+
+    assert_eq!(stats.char_count, 2000);
+    assert_eq!(stats.byte_len, 6000);
+    // every char adds 2 bytes of non ascii data
+    assert_eq!(stats.rollover_count, 4000 / 255);
+    assert!(!stats.is_ascii);
+    assert_eq!(
+        stats.index_overhead_bytes,
+        2000 + stats.rollover_count * core::mem::size_of::<usize>()
+    );
+    assert_eq!(stats.max_lookup_steps, 4);
+
+    // an ascii string allocates no index, however it became ascii
+    let edits: [fn(&mut OwnedIndexedChars); 7] = [
+        |s| {
+            let _ = s.pop();
+        },
+        |s| {
+            let _ = s.remove(500);
+        },
+        |s| s.truncate_chars(500),
+        |s| s.keep_char_range(..500),
+        |s| s.retain(|c| c.is_ascii()),
+        |s| {
+            let _ = s.replace_all_char('é', 'e');
+        },
+        |s| s.replace_char_range_with(500..501, &OwnedIndexedChars::new(String::from("e"))),
+    ];
+
+    for edit in edits {
+        let mut s = OwnedIndexedChars::new("a".repeat(500) + "é");
+        edit(&mut s);
+
+        let stats = s.index_stats();
+        assert!(stats.is_ascii);
+        assert_eq!(stats.index_overhead_bytes, 0);
+        assert_eq!((stats.rollover_count, stats.max_lookup_steps), (0, 0));
+    }
+}
+
+#[test]
+fn replace_char_range_with() {
+    let base = "aé💯b".repeat(200);
+
+    for replacement in ["", "xyz", "é", &"💯".repeat(100), &"aé💯b".repeat(200)] {
+        let replacement = OwnedIndexedChars::new(String::from(replacement));
+
+        for range in [0..0, 0..800, 1..3, 255..600, 799..800] {
+            let mut s = OwnedIndexedChars::new(base.clone());
+            s.replace_char_range_with(range.clone(), &replacement);
+
+            assert_eq!(s.inner, IndexedCharsInner::new(&s.buf));
+            assert_eq!(s.char_count(), 800 - range.len() + replacement.char_count());
+            assert_eq!(
+                s.get_char(range.start + replacement.char_count()),
+                base.chars().nth(range.end)
+            );
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn from_reader() {
+    /// A reader that returns a single byte per read
+    struct OneByte<'a>(&'a [u8]);
+
+    impl std::io::Read for OneByte<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            match (self.0.split_first(), buf.first_mut()) {
+                (Some((&b, rest)), Some(out)) => {
+                    *out = b;
+                    self.0 = rest;
+                    Ok(1)
+                }
+                _ => Ok(0),
+            }
+        }
+    }
+
+    let base = "aé💯b".repeat(3000);
+
+    let s = OwnedIndexedChars::from_reader(OneByte(base.as_bytes())).unwrap();
+    assert_eq!(s.buf, base);
+    assert_eq!(s.inner, IndexedCharsInner::new(&base));
+
+    let s = OwnedIndexedChars::from_reader(base.as_bytes()).unwrap();
+    assert_eq!(s.inner, IndexedCharsInner::new(&base));
+
+    let truncated = &base.as_bytes()[..base.len() - 2];
+    assert!(OwnedIndexedChars::from_reader(OneByte(truncated)).is_err());
+}
+
+#[cfg(feature = "unicode-width")]
+#[test]
+fn truncate_to_width() {
+    let base = "a中b文".repeat(100) + &"é".repeat(300);
+
+    for max_cols in [0, 1, 2, 3, 4, 5, 6, 299, 300, 301, 599, 600, 601, 1000] {
+        let mut s = OwnedIndexedChars::new(base.clone());
+        s.truncate_to_width(max_cols);
+
+        assert!(s.display_width() <= max_cols);
+        assert!(base.starts_with(s.as_str()));
+        assert_eq!(s.inner, IndexedCharsInner::new(&s.buf));
+
+        // the next char would not have fit
+        if let Some(next) = base[s.len()..].chars().next() {
+            assert!(s.display_width() + crate::width::char_width(next) > max_cols);
+        }
+    }
+}
+
+#[test]
+fn rotate_chars() {
+    let base = "aé💯b".repeat(200) + &"é".repeat(300);
+    let chars: Vec<_> = base.chars().collect();
+
+    for n in [0, 1, 2, 255, 256, 800, 1099, 1100, 1101, 5000] {
+        let mut left = OwnedIndexedChars::new(base.clone());
+        left.rotate_left_chars(n);
+
+        let mut expected = chars.clone();
+        expected.rotate_left(n % chars.len());
+
+        assert_eq!(left.to_char_vec(), expected);
+        assert_eq!(left.inner, IndexedCharsInner::new(&left.buf));
+
+        let mut right = OwnedIndexedChars::new(base.clone());
+        right.rotate_right_chars(n);
+
+        let mut expected = chars.clone();
+        expected.rotate_right(n % chars.len());
+
+        assert_eq!(right.to_char_vec(), expected);
+        assert_eq!(right.inner, IndexedCharsInner::new(&right.buf));
+    }
+
+    let mut empty = OwnedIndexedChars::new(String::new());
+    empty.rotate_left_chars(3);
+    empty.rotate_right_chars(3);
+    assert_eq!(empty, *"");
+}
+
+#[test]
+fn shrink_across_rollovers() {
+    // each "💯" adds 3 bytes of non ascii data, so this rolls over every 85 chars
+    let base = "💯".repeat(1000) + "a";
+
+    let check = |s: &mut OwnedIndexedChars| {
+        assert!(s.inner.matches(&s.buf));
+        assert_eq!(s.inner, IndexedCharsInner::new(&s.buf));
+
+        let count = s.char_count();
+        assert_eq!(s.get_char(count), None);
+        assert_eq!(s.get_char(count.wrapping_sub(1)), s.buf.chars().last());
+
+        // pushing after a shrink must not see any stale rollovers
+        s.push('é');
+        s.push_str(&"💯".repeat(100));
+        assert_eq!(s.inner, IndexedCharsInner::new(&s.buf));
+        s.truncate_chars(count);
+    };
+
+    for n in [1000, 999, 850, 500, 86, 85, 84, 1, 0] {
+        let mut s = OwnedIndexedChars::new(base.clone());
+
+        s.truncate_chars(n);
+        check(&mut s);
+
+        let mut s = OwnedIndexedChars::new(base.clone());
+
+        while s.char_count() > n {
+            s.pop();
+        }
+        check(&mut s);
+
+        let mut s = OwnedIndexedChars::new(base.clone());
+
+        while s.char_count() > n {
+            s.remove(n);
+        }
+        check(&mut s);
+
+        let mut s = OwnedIndexedChars::new(base.clone());
+
+        s.keep_char_range(..n);
+        check(&mut s);
+
+        let mut s = OwnedIndexedChars::new(base.clone());
+
+        _ = s.split_off_chars(n);
+        check(&mut s);
+    }
+}
+
+#[test]
+fn binary_search_char() {
+    let chars: Vec<char> = (0..0x3000)
+        .step_by(3)
+        .filter_map(char::from_u32)
+        .chain(['💯'; 10])
+        .collect();
+
+    let s = OwnedIndexedChars::from_chars(&chars);
+    assert!(s.chars_are_sorted());
+
+    for target in (0..0x3100)
+        .filter_map(char::from_u32)
+        .chain(['💯', '\u{10FFFF}'])
+    {
+        match (s.binary_search_char(target), chars.binary_search(&target)) {
+            (Ok(i), Ok(_)) => assert_eq!(chars[i], target),
+            (found, expected) => assert_eq!(found, expected),
+        }
+    }
+}
+
+#[test]
+fn replace_byte_range() {
+    let base = "aé💯b".repeat(200);
+
+    // byte ranges of whole chars, starting and ending on multi byte chars
+    for (range, replacement) in [
+        (0..0, "x"),
+        (1..3, ""),
+        (1..7, "ñ"),
+        (3..7, "abc"),
+        (699..1199, &"💯".repeat(100)),
+        (0..base.len(), "é"),
+    ] {
+        let mut s = OwnedIndexedChars::new(base.clone());
+        s.replace_byte_range(range.clone(), replacement);
+
+        let mut expected = base.clone();
+        expected.replace_range(range, replacement);
+
+        assert_eq!(s.buf, expected);
+        assert_eq!(s.inner, IndexedCharsInner::new(&s.buf));
+    }
+}
+
+#[test]
+fn split_terminator_char_indexed() {
+    let s = OwnedIndexedChars::new(String::from("ñ\n💯b\nç"));
+    let pieces: Vec<_> = s.split_terminator_char_indexed('\n').collect();
+    assert_eq!(pieces, [(0, "ñ"), (2, "💯b"), (5, "ç")]);
+
+    for &(index, piece) in &pieces {
+        assert_eq!(s.get_char(index), piece.chars().next());
+    }
+
+    let s = OwnedIndexedChars::new(String::from("ñ\n💯b\nç\n"));
+    let terminated: Vec<_> = s.split_terminator_char_indexed('\n').collect();
+    assert_eq!(terminated, pieces);
+
+    let s = OwnedIndexedChars::new(String::new());
+    assert_eq!(s.split_terminator_char_indexed('\n').count(), 0);
+}
+
+#[test]
+fn shrink_to() {
+    let mut s = OwnedIndexedChars::with_capacity_for(4096, 2048);
+    s.push_str(&"ñ💯a".repeat(100));
+
+    let len = s.buf.len();
+    let footprint = s.inner.memory_footprint();
+
+    for target in [2048, len, 10, 0] {
+        s.shrink_to(target);
+
+        assert!(s.buf.capacity() >= len);
+        assert!(s.buf.capacity() >= target.min(4096));
+        assert!(s.inner.memory_footprint() <= footprint);
+        assert!(s.inner.matches(&s.buf));
+    }
+
+    assert_eq!(s.buf.capacity(), len);
+    assert_eq!(s.inner.spare_capacity(), 0);
+    assert_eq!(s.get_char(298), Some('💯'));
+}
+
+#[test]
+fn common_prefix_len_with_str() {
+    // 'ï' and 'í' share their first utf8 byte
+    let s = OwnedIndexedChars::new(String::from("naïve"));
+    assert_eq!(s.common_prefix_len_with_str("naíve"), 2);
+    assert_eq!(s.common_prefix_len_with_str("naï"), 3);
+    assert_eq!(s.common_prefix_len_with_str("naïve"), 5);
+
+    // '💯' and '😀' share their first two utf8 bytes
+    let s = OwnedIndexedChars::new(String::from("ñ💯"));
+    assert_eq!(s.common_prefix_len_with_str("ñ😀"), 1);
+    assert_eq!(s.common_prefix_len_with_str("n💯"), 0);
+}
+
+#[test]
+fn optimize_index() {
+    let mut s = OwnedIndexedChars::new("aé💯".repeat(400));
+    let fresh_steps = s.max_lookup_steps();
+
+    for i in 0..50 {
+        s.insert(i * 17 % s.char_count(), '😀');
+        s.remove(i * 31 % s.char_count());
+        s.replace_char_range_with(100..110, &OwnedIndexedChars::new(String::from("ñ")));
+    }
+
+    let before = s.max_lookup_steps();
+    s.optimize_index();
+
+    assert!(s.max_lookup_steps() <= before);
+    assert!(s.max_lookup_steps() <= fresh_steps);
+    assert_eq!(s.inner, IndexedCharsInner::new(&s.buf));
+    assert_eq!(s.inner.spare_capacity(), 0);
+
+    for (i, c) in s.buf.chars().enumerate() {
+        assert_eq!(s.get_char(i), Some(c));
+    }
+    assert_eq!(s.get_char(s.char_count()), None);
+}
+
+#[test]
+fn content_fingerprint() {
+    let text = "aé💯b".repeat(100);
+
+    let new = OwnedIndexedChars::new(text.clone());
+    let chars: Vec<char> = text.chars().collect();
+    let from_chars = OwnedIndexedChars::from_chars(&chars);
+
+    let mut builder = crate::IndexBuilder::new();
+    for chunk in text.split_inclusive('💯') {
+        builder.push_str(chunk);
+    }
+    let built = builder.finish();
+
+    let mut edited = OwnedIndexedChars::with_capacity_for(4096, 4096);
+    edited.push_str("xyz");
+    edited.push_str(&text);
+    for _ in 0..3 {
+        edited.remove(0);
+    }
+
+    assert_ne!(edited.inner.spare_capacity(), new.inner.spare_capacity());
+
+    for other in [&from_chars, &built, &edited] {
+        assert_eq!(other.as_str(), new.as_str());
+        assert_eq!(other.content_fingerprint(), new.content_fingerprint());
+    }
+
+    assert_eq!(
+        OwnedIndexedChars::new(String::new()).content_fingerprint(),
+        0xcbf2_9ce4_8422_2325
+    );
+    assert_ne!(
+        OwnedIndexedChars::new(String::from("ab")).content_fingerprint(),
+        OwnedIndexedChars::new(String::from("ba")).content_fingerprint()
+    );
+}
+
+#[test]
+fn split_at_nth_char_match() {
+    let s = OwnedIndexedChars::new(String::from("ñañbñ"));
+
+    assert_eq!(s.split_at_nth_char_match('ñ', 0), Some(("", "ñañbñ")));
+    assert_eq!(s.split_at_nth_char_match('ñ', 1), Some(("ña", "ñbñ")));
+    assert_eq!(s.split_at_nth_char_match('ñ', 2), Some(("ñañb", "ñ")));
+    assert_eq!(s.split_at_nth_char_match('ñ', 3), None);
+    assert_eq!(s.split_at_nth_char_match('ñ', usize::MAX), None);
+    assert_eq!(s.split_at_nth_char_match('💯', 0), None);
+
+    for n in 0..3 {
+        let (before, after) = s.split_at_nth_char_match('ñ', n).unwrap();
+        assert_eq!(s.nth_char_match('ñ', n), Some(before.chars().count()));
+        assert_eq!([before, after].concat(), s.as_str());
+    }
+}
+
+#[test]
+fn index_snapshot() {
+    for text in [String::new(), String::from("abc"), "aé💯b".repeat(300)] {
+        let s = OwnedIndexedChars::new(text.clone());
+        let snapshot = s.index_snapshot();
+
+        let attached = OwnedIndexedChars::attach_index(text, &snapshot).unwrap();
+        assert_eq!(attached.inner, s.inner);
+        assert_eq!(attached.index_snapshot(), snapshot);
+    }
+
+    let text = "aé💯b".repeat(300);
+    let snapshot = OwnedIndexedChars::new(text.clone()).index_snapshot();
+    let kind = |snapshot: &[u8]| {
+        OwnedIndexedChars::attach_index(text.clone(), snapshot)
+            .unwrap_err()
+            .kind()
+    };
+
+    let mut corrupted = snapshot.clone();
+    corrupted[20] ^= 1;
+    assert_eq!(kind(&corrupted), IndexSnapshotErrorKind::Mismatch);
+
+    let mut corrupted = snapshot.clone();
+    corrupted[0] = 9;
+    assert_eq!(
+        kind(&corrupted),
+        IndexSnapshotErrorKind::UnsupportedVersion(9)
+    );
+
+    assert_eq!(
+        kind(&snapshot[..snapshot.len() - 1]),
+        IndexSnapshotErrorKind::Malformed
+    );
+    assert_eq!(kind(&snapshot[..5]), IndexSnapshotErrorKind::Malformed);
+    assert_eq!(kind(&[]), IndexSnapshotErrorKind::Malformed);
+
+    // a snapshot of a different string with the same byte length
+    let other = OwnedIndexedChars::new("aé💯b".repeat(299) + "ébbbb").index_snapshot();
+    let err = OwnedIndexedChars::attach_index(text.clone(), &other).unwrap_err();
+    assert_eq!(err.kind(), IndexSnapshotErrorKind::Mismatch);
+
+    let rebuilt = err.rebuild();
+    assert_eq!(rebuilt.as_str(), text);
+    assert_eq!(rebuilt.get_char(1001), Some('é'));
+
+    // rollovers are meaningless without offsets
+    let mut ascii = IndexedCharsInner::default().to_snapshot();
+    ascii.truncate(9);
+    ascii.extend_from_slice(&1u64.to_le_bytes());
+    ascii.extend_from_slice(&0u64.to_le_bytes());
+    assert_eq!(
+        OwnedIndexedChars::attach_index(String::from("abc"), &ascii)
+            .unwrap_err()
+            .kind(),
+        IndexSnapshotErrorKind::Mismatch
+    );
+}
+
+#[test]
+fn retain_indexed() {
+    let mut s = OwnedIndexedChars::new("aé💯b".repeat(300));
+    s.retain_indexed(|i, _| !(100..900).contains(&i));
+
+    assert_eq!(s.buf, "aé💯b".repeat(25) + &"aé💯b".repeat(75));
+    assert_eq!(s.inner, IndexedCharsInner::new(&s.buf));
+    assert_eq!(s.get_char(101), Some('é'));
+}
+
+#[test]
+fn insert_indexed() {
+    let base = "aé💯b".repeat(200);
+
+    for other in ["", "xyz", "é", &"💯".repeat(100), &"aé💯b".repeat(200)] {
+        let other = OwnedIndexedChars::new(String::from(other));
+
+        for index in [0, 1, 2, 3, 400, 800] {
+            let mut s = OwnedIndexedChars::new(base.clone());
+            s.insert_indexed(index, &other);
+
+            assert_eq!(s.inner, IndexedCharsInner::new(&s.buf));
+            assert_eq!(
+                s.get_char(index + other.char_count()),
+                base.chars().nth(index)
+            );
+        }
+    }
+
+    let mut ascii = OwnedIndexedChars::new(String::from("abc"));
+    ascii.insert_indexed(1, &OwnedIndexedChars::new(String::from("d")));
+    assert!(ascii.inner.is_ascii());
+}
+
+#[test]
+fn replace_all_char() {
+    let mut s = OwnedIndexedChars::new("aé💯b".repeat(200));
+
+    assert_eq!(s.replace_all_char('é', 'ø'), 200);
+    assert_eq!(s.inner, IndexedCharsInner::new(&s.buf));
+    assert_eq!(s.buf, "aø💯b".repeat(200));
+
+    assert_eq!(s.replace_all_char('💯', 'c'), 200);
+    assert_eq!(s.inner, IndexedCharsInner::new(&s.buf));
+    assert_eq!(s.buf, "aøcb".repeat(200));
+
+    assert_eq!(s.replace_all_char('x', 'y'), 0);
+}
+
+#[test]
+fn find_str_char_range() {
+    let s = OwnedIndexedChars::new(String::from("añ💯ñ💯"));
+    assert_eq!(s.find_str_char_range(""), Some(0..0));
+    assert_eq!(s.find_str_char_range("💯"), Some(2..3));
+    assert_eq!(s.find_str_char_range("ñ💯"), Some(1..3));
+    assert_eq!(s.find_str_char_range("añ💯ñ💯"), Some(0..5));
+    assert_eq!(s.find_str_char_range("💯a"), None);
+
+    let s = OwnedIndexedChars::new(String::from("abc"));
+    assert_eq!(s.find_str_char_range("c"), Some(2..3));
+
+    let s = OwnedIndexedChars::new(String::new());
+    assert_eq!(s.find_str_char_range(""), Some(0..0));
+    assert_eq!(s.find_str_char_range("a"), None);
+}
+
+#[cfg(feature = "unicode-case")]
+#[test]
+fn eq_ignore_case() {
+    let sharp = OwnedIndexedChars::new(String::from("ß"));
+    assert!(sharp.eq_ignore_case("ẞ"));
+    assert!(OwnedIndexedChars::new(String::from("ẞ")).eq_ignore_case("ß"));
+    assert!(!sharp.eq_ignore_case("ss"));
+    assert!(!sharp.eq_ignore_case("SS"));
+
+    let dotted = OwnedIndexedChars::new(String::from("İ"));
+    assert!(dotted.eq_ignore_case("İ"));
+    assert!(!dotted.eq_ignore_case("i"));
+    assert!(!dotted.eq_ignore_case("I"));
+    assert!(!dotted.eq_ignore_case("i\u{307}"));
+    assert!(!OwnedIndexedChars::new(String::from("ı")).eq_ignore_case("I"));
+
+    let s = OwnedIndexedChars::new(String::from("ǅungla \u{212A}elvin ΣΑΣ"));
+    assert!(s.eq_ignore_case("ǆUNGLA kelvin σας"));
+    assert!(!s.eq_ignore_case("ǆUNGLA kelvin σα"));
+}
+
+#[test]
+fn ascii_splice_allocates_nothing() {
+    let mut s = OwnedIndexedChars::new("a".repeat(100_000));
+
+    s.insert_indexed(1, &OwnedIndexedChars::new(String::from("b")));
+    assert_eq!(s.inner.memory_footprint(), 0);
+
+    s.replace_char_range_with(10..20, &OwnedIndexedChars::new(String::from("cd")));
+    assert_eq!(s.inner.memory_footprint(), 0);
+    assert_eq!(s.get_char(1), Some('b'));
+    assert_eq!(s.get_char(11), Some('d'));
+    assert_eq!(s.char_count(), 99_993);
+
+    s.insert_indexed(5, &OwnedIndexedChars::new(String::from("ñ")));
+    assert_eq!(s.get_char(5), Some('ñ'));
+    assert_eq!(s.get_char(12), Some('d'));
+}
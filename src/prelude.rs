@@ -0,0 +1,14 @@
+//! Convenience re-exports of the types of `char_index`.
+//!
+//! ```rust
+//! use char_index::prelude::*;
+//!
+//! let s = OwnedIndexedChars::new(String::from("foo"));
+//! # assert_eq!(s.get_char(0), Some('f'));
+//! ```
+
+pub use crate::{
+    BlockInfo, BoxedIndexedChars, CharCursor, CharEdit, IndexBuilder, IndexSnapshotError,
+    IndexSnapshotErrorKind, IndexStats, IndexedChars, LineIndexedChars, OwnedIndexedChars,
+    RawPartsError, SharedIndexedChars, Utf16View,
+};
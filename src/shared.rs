@@ -0,0 +1,182 @@
+//! Module containing [`SharedIndexedChars`] and its trait implementations
+
+use alloc::sync::Arc;
+use core::{
+    borrow::Borrow,
+    cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd},
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+
+use crate::{IndexedCharsInner, OwnedIndexedChars};
+
+/// A string whose char indices have been cached for ~O(1) char lookup. Shared, read only variant.
+///
+/// This is constructed with [`OwnedIndexedChars::into_shared`], and stores both its string and its index behind an `Arc`,
+/// so cloning it is O(1) and shares the index instead of copying it. It is intended for immutable documents shared between threads.
+///
+/// This type is immutable, to edit it convert it back with [`to_owned_indexed`][SharedIndexedChars::to_owned_indexed],
+/// which copies the string and its index.
+///
+/// This type mimics an `Arc<str>` with its trait impls, including `Debug`, `Display`, `PartialEq` with `&str` `PartialOrd` with `&str`, `Hash`, and `AsRef`/`Borrow`.
+#[derive(Clone)]
+pub struct SharedIndexedChars {
+    /// Backing string allocation
+    buf: Arc<str>,
+    /// Char offsets index
+    inner: Arc<IndexedCharsInner>,
+}
+
+impl SharedIndexedChars {
+    /// Constructs a new [`SharedIndexedChars`] from a string and an index that was built for that string,
+    /// shrinking the index to fit.
+    pub(crate) fn from_inner(buf: &str, mut inner: IndexedCharsInner) -> Self {
+        inner.shrink_to_fit();
+
+        Self {
+            buf: Arc::from(buf),
+            inner: Arc::new(inner),
+        }
+    }
+
+    /// Indexes into the backing string to retrieve the nth codepoint.
+    ///
+    /// This operation has an average case of O(1), and a worst case of O(log n).
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("foo")).into_shared();
+    ///
+    /// assert_eq!(s.get_char(1), Some('o'));
+    /// ```
+    #[must_use]
+    pub fn get_char(&self, index: usize) -> Option<char> {
+        self.inner.get_char(&self.buf, index)
+    }
+
+    /// Returns the number of chars present in the backing string, this operation is free thanks to
+    /// how [`SharedIndexedChars`] is constructed
+    #[must_use]
+    pub fn char_count(&self) -> usize {
+        self.inner.char_count(&self.buf)
+    }
+
+    /// Returns a reference to the backing `Arc<str>` as a `&str`.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.buf
+    }
+
+    /// Copies the string and its index into a new [`OwnedIndexedChars`] so that it may be mutated.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("añb")).into_shared();
+    /// let mut owned = s.to_owned_indexed();
+    ///
+    /// owned.push('c');
+    /// assert_eq!(owned.as_str(), "añbc");
+    /// # assert_eq!(s.as_str(), "añb");
+    /// ```
+    #[must_use]
+    pub fn to_owned_indexed(&self) -> OwnedIndexedChars {
+        OwnedIndexedChars::from_inner((*self.buf).into(), (*self.inner).clone())
+    }
+}
+
+// The following lines are all trait implementations made to mirror what str does, and be compatible with str
+
+impl Deref for SharedIndexedChars {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.buf
+    }
+}
+
+impl AsRef<str> for SharedIndexedChars {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl Borrow<str> for SharedIndexedChars {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl fmt::Debug for SharedIndexedChars {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        <str as fmt::Debug>::fmt(&self.buf, f)
+    }
+}
+
+impl fmt::Display for SharedIndexedChars {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        <str as fmt::Display>::fmt(&self.buf, f)
+    }
+}
+
+impl Eq for SharedIndexedChars {}
+
+impl PartialEq for SharedIndexedChars {
+    fn eq(&self, other: &Self) -> bool {
+        self.buf.eq(&other.buf)
+    }
+}
+
+impl PartialEq<str> for SharedIndexedChars {
+    fn eq(&self, other: &str) -> bool {
+        (*self.buf).eq(other)
+    }
+}
+
+impl PartialEq<SharedIndexedChars> for str {
+    fn eq(&self, other: &SharedIndexedChars) -> bool {
+        self.eq(&*other.buf)
+    }
+}
+
+impl Ord for SharedIndexedChars {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.buf.cmp(&other.buf)
+    }
+}
+
+impl PartialOrd for SharedIndexedChars {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialOrd<str> for SharedIndexedChars {
+    fn partial_cmp(&self, other: &str) -> Option<Ordering> {
+        Some((*self.buf).cmp(other))
+    }
+}
+
+impl PartialOrd<SharedIndexedChars> for str {
+    fn partial_cmp(&self, other: &SharedIndexedChars) -> Option<Ordering> {
+        Some(self.cmp(&*other.buf))
+    }
+}
+
+impl Hash for SharedIndexedChars {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.buf.hash(state);
+    }
+}
+
+#[test]
+fn clone_shares_index() {
+    let s = OwnedIndexedChars::new("aé💯".repeat(1000)).into_shared();
+    let clone = s.clone();
+
+    assert!(Arc::ptr_eq(&s.buf, &clone.buf));
+    assert!(Arc::ptr_eq(&s.inner, &clone.inner));
+    assert_eq!(clone.get_char(2999), Some('💯'));
+}
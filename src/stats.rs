@@ -0,0 +1,35 @@
+//! Module containing introspection types describing the shape of an index
+
+use core::ops::Range;
+
+/// Describes a block of an index, the run of chars between two internal rollovers.
+///
+/// All chars in a block are resolved with the same rollover adjustment, see the section
+/// [`How it Works`](index.html#how-it-works) for details.
+/// This is returned by [`OwnedIndexedChars::block_of_char`](crate::OwnedIndexedChars::block_of_char).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockInfo {
+    /// The char indices of the chars in this block
+    pub chars: Range<usize>,
+    /// The byte offsets of the chars in this block
+    pub bytes: Range<usize>,
+}
+
+/// A summary of the shape and cost of an index, as returned by [`OwnedIndexedChars::index_stats`](crate::OwnedIndexedChars::index_stats).
+///
+/// See the section [`How it Works`](index.html#how-it-works) for details on what these values mean for lookups.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexStats {
+    /// The number of chars in the string
+    pub char_count: usize,
+    /// The length of the string in bytes
+    pub byte_len: usize,
+    /// The number of internal rollovers, which occur after each 255 bytes of non ascii data
+    pub rollover_count: usize,
+    /// Whether the string is only ascii, in which case the index allocates nothing and lookups are O(1)
+    pub is_ascii: bool,
+    /// The amount of heap memory allocated by the index in bytes, including spare capacity but not counting the string itself
+    pub index_overhead_bytes: usize,
+    /// The number of steps the rollover search takes for a lookup at the farthest char index, 0 without rollovers
+    pub max_lookup_steps: usize,
+}
@@ -0,0 +1,172 @@
+//! Module containing [`Utf16View`], a table for converting between char indices and utf16 offsets
+
+use alloc::vec::Vec;
+
+use crate::OwnedIndexedChars;
+
+/// The number of chars between each recorded utf16 offset
+const STRIDE: usize = 64;
+
+/// A view of an [`OwnedIndexedChars`] that converts between char indices and utf16 code unit offsets, as used by LSP positions.
+///
+/// This is constructed with [`OwnedIndexedChars::utf16_view`], which records the utf16 offset of every 64th char.
+/// Conversions then find the nearest recorded offset and walk at most 64 chars from there, using the char index
+/// to jump to the start of the walk, so repeated queries against the same document stay cheap.
+/// Strings where every char is a single utf16 code unit record nothing, as char indices and utf16 offsets are equal.
+///
+/// # Examples
+/// ```rust
+/// # use char_index::OwnedIndexedChars;
+/// let s = OwnedIndexedChars::new(String::from("a💯b"));
+/// let view = s.utf16_view();
+///
+/// assert_eq!(view.char_to_utf16(2), Some(3));
+/// assert_eq!(view.utf16_to_char(3), Some(2));
+/// // utf16 offset 2 is in the middle of the surrogate pair of 💯
+/// assert_eq!(view.utf16_to_char(2), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Utf16View<'a> {
+    /// Indexed string being viewed
+    text: &'a OwnedIndexedChars,
+    /// Utf16 offset of every `STRIDE`th char, empty if every char is a single code unit
+    checkpoints: Vec<usize>,
+    /// Length of the string in utf16 code units
+    utf16_len: usize,
+}
+
+impl<'a> Utf16View<'a> {
+    /// Constructs a new [`Utf16View`] of `text`, walking it once to record utf16 offsets.
+    pub(crate) fn new(text: &'a OwnedIndexedChars) -> Self {
+        let mut checkpoints = Vec::with_capacity(text.char_count() / STRIDE + 1);
+        let mut utf16_len = 0;
+
+        for (i, c) in text.chars().enumerate() {
+            if i % STRIDE == 0 {
+                checkpoints.push(utf16_len);
+            }
+
+            utf16_len += c.len_utf16();
+        }
+
+        if utf16_len == text.char_count() {
+            checkpoints = Vec::new();
+        }
+
+        Self {
+            text,
+            checkpoints,
+            utf16_len,
+        }
+    }
+
+    /// Returns the length of the string in utf16 code units.
+    #[must_use]
+    pub fn utf16_len(&self) -> usize {
+        self.utf16_len
+    }
+
+    /// Converts a char index into a utf16 offset, or returns `None` if `index` is greater than the char count.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("💯ñ💯"));
+    /// let view = s.utf16_view();
+    ///
+    /// assert_eq!(view.char_to_utf16(1), Some(2));
+    /// assert_eq!(view.char_to_utf16(3), Some(5));
+    /// assert_eq!(view.char_to_utf16(4), None);
+    /// ```
+    #[must_use]
+    pub fn char_to_utf16(&self, index: usize) -> Option<usize> {
+        if index == self.text.char_count() {
+            return Some(self.utf16_len);
+        }
+
+        if self.checkpoints.is_empty() {
+            return (index < self.text.char_count()).then(|| index);
+        }
+
+        let block = index / STRIDE;
+        let walked = self.text.get_char_range(block * STRIDE..index)?;
+
+        Some(self.checkpoints[block] + walked.chars().map(char::len_utf16).sum::<usize>())
+    }
+
+    /// Converts a utf16 offset into a char index, or returns `None` if `offset` is greater than the utf16 length
+    /// or is in the middle of a surrogate pair.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("💯ñ💯"));
+    /// let view = s.utf16_view();
+    ///
+    /// assert_eq!(view.utf16_to_char(2), Some(1));
+    /// assert_eq!(view.utf16_to_char(5), Some(3));
+    /// assert_eq!(view.utf16_to_char(4), None);
+    /// ```
+    #[must_use]
+    pub fn utf16_to_char(&self, offset: usize) -> Option<usize> {
+        if offset > self.utf16_len {
+            return None;
+        }
+
+        if self.checkpoints.is_empty() {
+            return Some(offset);
+        }
+
+        // the first checkpoint is always 0, so at least one checkpoint is at or before offset
+        let block = self.checkpoints.partition_point(|&c| c <= offset) - 1;
+        let start = block * STRIDE;
+
+        let mut pos = self.checkpoints[block];
+
+        for (i, c) in self.text.get_char_range(start..)?.chars().enumerate() {
+            if pos >= offset {
+                return (pos == offset).then(|| start + i);
+            }
+
+            pos += c.len_utf16();
+        }
+
+        (pos == offset).then(|| self.text.char_count())
+    }
+}
+
+#[test]
+fn matches_encode_utf16() {
+    use alloc::string::String;
+
+    for base in [
+        String::new(),
+        String::from("abc"),
+        "añ".repeat(100),
+        "a💯é𝄞b".repeat(100),
+        "💯".repeat(129),
+    ] {
+        let s = OwnedIndexedChars::new(base.clone());
+        let view = s.utf16_view();
+
+        assert_eq!(view.utf16_len(), base.encode_utf16().count());
+
+        let mut utf16 = 0;
+
+        for (i, c) in base.chars().enumerate() {
+            assert_eq!(view.char_to_utf16(i), Some(utf16));
+            assert_eq!(view.utf16_to_char(utf16), Some(i));
+
+            if c.len_utf16() == 2 {
+                assert_eq!(view.utf16_to_char(utf16 + 1), None);
+            }
+
+            utf16 += c.len_utf16();
+        }
+
+        assert_eq!(view.char_to_utf16(s.char_count()), Some(utf16));
+        assert_eq!(view.utf16_to_char(utf16), Some(s.char_count()));
+        assert_eq!(view.char_to_utf16(s.char_count() + 1), None);
+        assert_eq!(view.utf16_to_char(utf16 + 1), None);
+    }
+}
@@ -0,0 +1,84 @@
+//! Module containing the approximate terminal display width of chars, used by
+//! [`OwnedIndexedChars::display_width`](crate::OwnedIndexedChars::display_width)
+
+/// Ranges of chars that take up no columns: combining marks, zero width spaces and joiners, and variation selectors
+const ZERO_WIDTH: &[(char, char)] = &[
+    ('\u{0300}', '\u{036F}'),
+    ('\u{0483}', '\u{0489}'),
+    ('\u{0591}', '\u{05BD}'),
+    ('\u{0610}', '\u{061A}'),
+    ('\u{064B}', '\u{065F}'),
+    ('\u{1160}', '\u{11FF}'),
+    ('\u{1AB0}', '\u{1AFF}'),
+    ('\u{1DC0}', '\u{1DFF}'),
+    ('\u{200B}', '\u{200F}'),
+    ('\u{2060}', '\u{2064}'),
+    ('\u{20D0}', '\u{20FF}'),
+    ('\u{FE00}', '\u{FE0F}'),
+    ('\u{FE20}', '\u{FE2F}'),
+    ('\u{FEFF}', '\u{FEFF}'),
+    ('\u{E0100}', '\u{E01EF}'),
+];
+
+/// Ranges of chars that take up two columns: the major East Asian wide and fullwidth blocks, and emoji
+const WIDE: &[(char, char)] = &[
+    ('\u{1100}', '\u{115F}'),
+    ('\u{2E80}', '\u{303E}'),
+    ('\u{3041}', '\u{33FF}'),
+    ('\u{3400}', '\u{4DBF}'),
+    ('\u{4E00}', '\u{9FFF}'),
+    ('\u{A000}', '\u{A4CF}'),
+    ('\u{A960}', '\u{A97F}'),
+    ('\u{AC00}', '\u{D7A3}'),
+    ('\u{F900}', '\u{FAFF}'),
+    ('\u{FE10}', '\u{FE19}'),
+    ('\u{FE30}', '\u{FE6F}'),
+    ('\u{FF00}', '\u{FF60}'),
+    ('\u{FFE0}', '\u{FFE6}'),
+    ('\u{1F300}', '\u{1F64F}'),
+    ('\u{1F900}', '\u{1F9FF}'),
+    ('\u{20000}', '\u{2FFFD}'),
+    ('\u{30000}', '\u{3FFFD}'),
+];
+
+/// Checks whether `c` is within any of the sorted, non overlapping inclusive `ranges`
+fn in_ranges(ranges: &[(char, char)], c: char) -> bool {
+    let i = ranges.partition_point(|&(_, end)| end < c);
+
+    ranges.get(i).map_or(false, |&(start, _)| start <= c)
+}
+
+/// Returns the approximate number of terminal columns `c` takes up
+pub(crate) fn char_width(c: char) -> usize {
+    if c.is_ascii() {
+        return usize::from(!c.is_ascii_control());
+    }
+
+    if c.is_control() || in_ranges(ZERO_WIDTH, c) {
+        0
+    } else if in_ranges(WIDE, c) {
+        2
+    } else {
+        1
+    }
+}
+
+#[test]
+fn sorted_ranges() {
+    for ranges in [ZERO_WIDTH, WIDE] {
+        for &(start, end) in ranges {
+            assert!(start <= end);
+        }
+
+        for pair in ranges.windows(2) {
+            assert!(pair[0].1 < pair[1].0);
+        }
+    }
+
+    assert_eq!(char_width('a'), 1);
+    assert_eq!(char_width('\n'), 0);
+    assert_eq!(char_width('\u{0301}'), 0);
+    assert_eq!(char_width('中'), 2);
+    assert_eq!(char_width('💯'), 2);
+    assert_eq!(char_width('ñ'), 1);
+}